@@ -0,0 +1,109 @@
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError};
+use aoc_parse::{blocks, parse_all, separated, unsigned, Parsed};
+
+#[derive(Debug)]
+pub struct Food {
+    pub calories: u64,
+}
+
+#[derive(Debug)]
+pub struct Elf {
+    pub food: Vec<Food>,
+}
+
+impl Elf {
+    /// One number of calories on each line
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (calories, rest) = separated(unsigned, "\n")(input)?;
+        let food = calories
+            .into_iter()
+            .map(|calories| Food { calories })
+            .collect();
+
+        Ok((Elf { food }, rest))
+    }
+
+    fn calories(&self) -> u64 {
+        self.food.iter().map(|f| f.calories).sum()
+    }
+}
+
+pub struct CalorieCounting;
+
+impl Solution for CalorieCounting {
+    const DAY: u8 = 1;
+    const NAME: &'static str = "Calorie Counting";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
+
+    type Input = Vec<Elf>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, blocks(Elf::parse)).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(elves: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        elves
+            .iter()
+            .map(|e| e.calories())
+            .max()
+            .ok_or_else(|| SolveError::NoAnswer("there are no elves".into()))
+    }
+
+    fn part_2(elves: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        let mut calories: Vec<u64> = elves.iter().map(|e| e.calories()).collect();
+
+        if calories.len() < 3 {
+            return Err(SolveError::NoAnswer(format!(
+                "there are only {} elves to pick the top three from",
+                calories.len()
+            )));
+        }
+
+        calories.sort();
+        calories.reverse();
+
+        Ok(calories[0..3].iter().sum())
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u64, Error> {
+    let elves = CalorieCounting::parse(input)?;
+
+    Ok(CalorieCounting::part_1(&elves, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<u64, Error> {
+    let elves = CalorieCounting::parse(input)?;
+
+    Ok(CalorieCounting::part_2(&elves, &Params::default())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn errors_instead_of_panicking() {
+        let elves = CalorieCounting::parse("1\n\n2\n").unwrap();
+
+        assert_eq!(CalorieCounting::part_1(&elves, &Params::default()), Ok(2));
+        assert!(matches!(
+            CalorieCounting::part_2(&elves, &Params::default()),
+            Err(SolveError::NoAnswer(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[0-9a\\n ]{0,64}") {
+            let _ = CalorieCounting::parse(&input);
+        }
+    }
+}
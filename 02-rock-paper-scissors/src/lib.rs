@@ -0,0 +1,215 @@
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError};
+use aoc_parse::{lines, one_of, parse_all, tag, Parsed};
+
+/// A single line of the strategy guide
+#[derive(Debug)]
+pub struct GuideLine {
+    opponent_move: Move,
+    second_column: Column,
+}
+
+impl GuideLine {
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (opponent_move, input) = Move::parse(input)?;
+        let (_, input) = tag(" ")(input)?;
+        let (second_column, input) = Column::parse(input)?;
+
+        Ok((
+            GuideLine {
+                opponent_move,
+                second_column,
+            },
+            input,
+        ))
+    }
+}
+
+/// We don't know what the second column means until we know which part we
+/// are solving, so we hold onto which letter it was until then
+#[derive(Debug, Clone, Copy)]
+enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl Column {
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        const COLUMNS: &[(&str, Column)] = &[("X", Column::X), ("Y", Column::Y), ("Z", Column::Z)];
+
+        one_of(COLUMNS, "one of X, Y or Z")(input)
+    }
+
+    /// In part 1 we think the column is the move we should play
+    fn as_move(&self) -> Move {
+        match self {
+            Column::X => Move::Rock,
+            Column::Y => Move::Paper,
+            Column::Z => Move::Scissors,
+        }
+    }
+
+    /// In part 2 we find out the column is how the round needs to end
+    fn as_outcome(&self) -> Outcome {
+        match self {
+            Column::X => Outcome::Lose,
+            Column::Y => Outcome::Draw,
+            Column::Z => Outcome::Win,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Round {
+    opponent_move: Move,
+    your_move: Move,
+}
+
+impl Round {
+    fn from_line_part1(line: &GuideLine) -> Self {
+        let your_move = line.second_column.as_move();
+
+        Round {
+            opponent_move: line.opponent_move,
+            your_move,
+        }
+    }
+
+    fn from_line_part2(line: &GuideLine) -> Self {
+        let end_result = line.second_column.as_outcome();
+
+        let your_move = line.opponent_move.your_move_for_result(end_result);
+
+        Round {
+            opponent_move: line.opponent_move,
+            your_move,
+        }
+    }
+
+    fn outcome_score(&self) -> u64 {
+        self.your_move.outcome_score(&self.opponent_move)
+    }
+
+    fn score(&self) -> u64 {
+        self.outcome_score() + self.your_move.score()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl Move {
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        const MOVES: &[(&str, Move)] =
+            &[("A", Move::Rock), ("B", Move::Paper), ("C", Move::Scissors)];
+
+        one_of(MOVES, "one of A, B or C")(input)
+    }
+
+    fn score(&self) -> u64 {
+        match self {
+            Move::Rock => 1,
+            Move::Paper => 2,
+            Move::Scissors => 3,
+        }
+    }
+
+    fn outcome_score(&self, other: &Self) -> u64 {
+        match (self, other) {
+            (Move::Rock, Move::Scissors) => 6,
+            (Move::Paper, Move::Rock) => 6,
+            (Move::Scissors, Move::Paper) => 6,
+            (Move::Rock, Move::Paper) => 0,
+            (Move::Paper, Move::Scissors) => 0,
+            (Move::Scissors, Move::Rock) => 0,
+            (Move::Scissors, Move::Scissors) => 3,
+            (Move::Rock, Move::Rock) => 3,
+            (Move::Paper, Move::Paper) => 3,
+        }
+    }
+
+    /// This method takes in the intended result, and returns the move you
+    /// have to play to get the Outcome in the round
+    fn your_move_for_result(&self, intended_result: Outcome) -> Self {
+        match (self, intended_result) {
+            (Move::Rock, Outcome::Draw) => Move::Rock,
+            (Move::Rock, Outcome::Win) => Move::Paper,
+            (Move::Rock, Outcome::Lose) => Move::Scissors,
+
+            (Move::Paper, Outcome::Draw) => Move::Paper,
+            (Move::Paper, Outcome::Win) => Move::Scissors,
+            (Move::Paper, Outcome::Lose) => Move::Rock,
+
+            (Move::Scissors, Outcome::Draw) => Move::Scissors,
+            (Move::Scissors, Outcome::Win) => Move::Rock,
+            (Move::Scissors, Outcome::Lose) => Move::Paper,
+        }
+    }
+}
+
+pub struct RockPaperScissors;
+
+impl Solution for RockPaperScissors {
+    const DAY: u8 = 2;
+    const NAME: &'static str = "Rock Paper Scissors";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
+
+    type Input = Vec<GuideLine>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, lines(GuideLine::parse)).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(guide: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        let rounds: Vec<Round> = guide.iter().map(Round::from_line_part1).collect();
+
+        Ok(rounds.iter().map(|r| r.score()).sum())
+    }
+
+    fn part_2(guide: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        let rounds: Vec<Round> = guide.iter().map(Round::from_line_part2).collect();
+
+        Ok(rounds.iter().map(|r| r.score()).sum())
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u64, Error> {
+    let guide = RockPaperScissors::parse(input)?;
+
+    Ok(RockPaperScissors::part_1(&guide, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<u64, Error> {
+    let guide = RockPaperScissors::parse(input)?;
+
+    Ok(RockPaperScissors::part_2(&guide, &Params::default())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[A-DW-Z \\n]{0,64}") {
+            let _ = RockPaperScissors::parse(&input);
+        }
+    }
+}
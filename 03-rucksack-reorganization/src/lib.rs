@@ -1,15 +1,27 @@
 use std::collections::HashSet;
 
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_parse::{chars, lines, parse_all, Parsed};
+
 #[derive(Debug)]
-struct Rucksack {
+pub struct Rucksack {
     pub items: Vec<char>,
 }
 
 impl Rucksack {
-    fn parse(input: &str) -> Self {
-        let items = input.chars().collect();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (items, rest) = chars("an item from a-z or A-Z", |c| c.is_ascii_alphabetic())(input)?;
+
+        if !items.len().is_multiple_of(2) {
+            return Err(Unexpected::new(
+                items,
+                "an even number of items to split between the compartments",
+            ));
+        }
+
+        let items = items.chars().collect();
 
-        Self { items }
+        Ok((Self { items }, rest))
     }
 
     fn compartments(&self) -> (Vec<char>, Vec<char>) {
@@ -42,63 +54,85 @@ pub fn char_to_score(c: char) -> u64 {
     }
 }
 
-pub fn part_1(input: &str) -> u64 {
-    let sacks: Vec<_> = input.trim().lines().map(Rucksack::parse).collect();
+pub struct RucksackReorganization;
 
-    let first_issue = sacks.iter().find(|s| s.matching_char().is_none());
-    dbg!(first_issue);
+impl Solution for RucksackReorganization {
+    const DAY: u8 = 3;
+    const NAME: &'static str = "Rucksack Reorganization";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
 
-    let matching: Vec<_> = sacks.iter().map(|s| s.matching_char()).collect();
-    // dbg!(&matching);
+    type Input = Vec<Rucksack>;
+    type Part1 = u64;
+    type Part2 = u64;
 
-    matching
-        .iter()
-        .map(|c| {
-            let c = c.expect("There should always be a char in both compartments");
-            char_to_score(c)
-        })
-        .sum()
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, lines(Rucksack::parse)).map_err(|e| e.locate(Self::DAY, input))
+    }
 
-pub fn part_2(input: &str) -> u64 {
-    let sacks: Vec<_> = input.trim().lines().map(Rucksack::parse).collect();
-    let groups: Vec<_> = sacks.chunks(3).collect();
+    fn part_1(sacks: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        let matching = sacks.iter().enumerate().map(|(i, s)| {
+            s.matching_char().ok_or_else(|| {
+                SolveError::NoAnswer(format!(
+                    "rucksack {} has nothing in both compartments",
+                    i + 1
+                ))
+            })
+        });
+
+        matching.map(|c| c.map(char_to_score)).sum()
+    }
 
-    let badges = groups.iter().map(|group| {
-        let in_all = group
-            .iter()
-            .map(|s| s.items.iter().cloned().collect())
-            .reduce(|x: HashSet<char>, y| x.intersection(&y).cloned().collect())
-            .unwrap();
+    fn part_2(sacks: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        if !sacks.len().is_multiple_of(3) {
+            return Err(SolveError::NoAnswer(format!(
+                "{} rucksacks don't split into groups of three",
+                sacks.len()
+            )));
+        }
+
+        let groups: Vec<_> = sacks.chunks(3).collect();
+
+        let badges = groups.iter().enumerate().map(|(i, group)| {
+            let in_all = group
+                .iter()
+                .map(|s| s.items.iter().cloned().collect())
+                .reduce(|x: HashSet<char>, y| x.intersection(&y).cloned().collect())
+                .unwrap_or_default();
+
+            match Vec::from_iter(in_all)[..] {
+                [badge] => Ok(badge),
+                ref in_all => Err(SolveError::NoAnswer(format!(
+                    "group {} has {} items in common rather than one badge",
+                    i + 1,
+                    in_all.len()
+                ))),
+            }
+        });
+
+        badges.map(|badge| badge.map(char_to_score)).sum()
+    }
+}
 
-        let in_all: Vec<char> = in_all.into_iter().collect();
-        debug_assert_eq!(in_all.len(), 1);
+pub fn part_1(input: &str) -> Result<u64, Error> {
+    let sacks = RucksackReorganization::parse(input)?;
 
-        in_all[0]
-    });
+    Ok(RucksackReorganization::part_1(&sacks, &Params::default())?)
+}
 
-    badges.map(char_to_score).sum()
+pub fn part_2(input: &str) -> Result<u64, Error> {
+    let sacks = RucksackReorganization::parse(input)?;
+
+    Ok(RucksackReorganization::part_2(&sacks, &Params::default())?)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use proptest::prelude::*;
 
-    #[test]
-    fn example_input_part_1() {
-        let example_input = include_str!("example.input");
-        let ans = part_1(example_input);
-
-        assert_eq!(ans, 157)
-    }
-
-    #[test]
-    fn my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = part_1(input);
-
-        assert_eq!(ans, 8085)
-    }
+    use super::*;
 
     #[test]
     fn char_scores() {
@@ -109,18 +143,39 @@ mod tests {
     }
 
     #[test]
-    fn example_input_part_2() {
-        let example_input = include_str!("example.input");
-        let ans = part_2(example_input);
-
-        assert_eq!(ans, 70)
+    fn errors_instead_of_panicking() {
+        let sacks = RucksackReorganization::parse("ab\n").unwrap();
+        assert!(matches!(
+            RucksackReorganization::part_1(&sacks, &Params::default()),
+            Err(SolveError::NoAnswer(_))
+        ));
+
+        for input in ["aa\nbb\n", "aa\nbb\ncc\n", "abab\nabab\nabab\n"] {
+            let sacks = RucksackReorganization::parse(input).unwrap();
+
+            assert!(
+                matches!(
+                    RucksackReorganization::part_2(&sacks, &Params::default()),
+                    Err(SolveError::NoAnswer(_))
+                ),
+                "{input:?}"
+            );
+        }
     }
 
     #[test]
-    fn my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = part_2(input);
+    fn parse_error_on_non_letter_item() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGL1vFMfFMH\n";
+        let err = RucksackReorganization::parse(input).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 17));
+        assert_eq!(err.text, "1");
+    }
 
-        assert_eq!(ans, 2515)
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[a-zA-Z1 \\n]{0,64}") {
+            let _ = RucksackReorganization::parse(&input);
+        }
     }
 }
@@ -1,151 +1,147 @@
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_geometry::Interval;
+use aoc_parse::{label, lines, parse_all, spanned, tag, unsigned, Parsed};
+
+/// The sections each of a pair of elves was given to clean up
 #[derive(Clone, Debug)]
-struct Pair {
-    left: Assignment,
-    right: Assignment,
+pub struct Pair {
+    left: Interval,
+    right: Interval,
 }
 
 impl Pair {
-    fn parse(input: &str) -> Self {
-        let p: Vec<_> = input.split(',').map(Assignment::parse).collect();
-
-        debug_assert_eq!(
-            p.len(),
-            2,
-            "If we got anything but 2 segments here our parsing must be off"
-        );
-
-        Self {
-            left: p[0].clone(),
-            right: p[1].clone(),
-        }
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (left, input) = assignment(input)?;
+        let (_, input) = label(tag(","), "a comma between the two elves")(input)?;
+        let (right, input) = assignment(input)?;
+
+        Ok((Self { left, right }, input))
     }
 
     fn is_fully_contained(&self) -> bool {
-        self.left_contains_right() || self.right_contains_left()
+        self.left.covers(self.right) || self.right.covers(self.left)
     }
 
-    fn left_contains_right(&self) -> bool {
-        self.left.from <= self.right.from && self.left.to >= self.right.to
+    fn is_overlapping(&self) -> bool {
+        self.left.overlaps(self.right)
     }
+}
 
-    fn right_contains_left(&self) -> bool {
-        self.right.from <= self.left.from && self.right.to >= self.left.to
-    }
+/// A range of sections like `2-4`
+fn assignment(input: &str) -> Parsed<'_, Interval> {
+    let range = |input| {
+        let (from, input) = unsigned(input)?;
+        let (_, input) = tag("-")(input)?;
+        let (to, input) = unsigned(input)?;
 
-    fn is_overlapping(&self) -> bool {
-        self.left_overlaps_right() || self.right_overlaps_left() || self.is_fully_contained()
-    }
+        Ok(((from, to), input))
+    };
+    let (range, rest) = spanned(label(range, "a range of sections like 2-4"))(input)?;
 
-    /// LLLLLL
-    ///   RRRRRR
-    /// This overlaps
-    ///
-    /// LLLLLLLLLLL
-    ///    RRRRR
-    /// This does NOT overlap on the border
-    ///
-    /// LLLLLLL
-    ///           RRRRRR
-    /// Does NOT overlap
-    fn left_overlaps_right(&self) -> bool {
-        self.left.from <= self.right.from
-            && self.left.to <= self.right.to
-            && self.right.from <= self.left.to
-    }
+    let (from, to) = range.value;
+    let assignment = Interval::new(from, to)
+        .ok_or_else(|| Unexpected::new(range.span, "a range that ends after it starts"))?;
 
-    //      LLLLLLLL
-    //   RRRRRRR
-    // DOES
-    //
-    //         LLLLLLLLL
-    //  RRRRRR
-    //  does NOT
-    fn right_overlaps_left(&self) -> bool {
-        self.right.from <= self.left.from
-            && self.right.to <= self.left.to
-            && self.left.from <= self.right.to
-    }
+    Ok((assignment, rest))
 }
 
-#[derive(Clone, Debug)]
-struct Assignment {
-    from: u64,
-    to: u64,
-}
+pub struct CampCleanup;
 
-impl Assignment {
-    fn parse(input: &str) -> Self {
-        let split: Vec<u64> = input.split('-').map(|s| s.parse().unwrap()).collect();
+impl Solution for CampCleanup {
+    const DAY: u8 = 4;
+    const NAME: &'static str = "Camp Cleanup";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
 
-        debug_assert_eq!(
-            split.len(),
-            2,
-            "If we got anything but 2 segments here our parsing must be off"
-        );
+    type Input = Vec<Pair>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-        Self {
-            from: split[0],
-            to: split[1],
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, lines(Pair::parse)).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(pairs: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        Ok(pairs.iter().filter(|p| p.is_fully_contained()).count())
+    }
+
+    fn part_2(pairs: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        Ok(pairs.iter().filter(|p| p.is_overlapping()).count())
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    let pairs: Vec<Pair> = input.lines().map(Pair::parse).collect();
+pub fn part_1(input: &str) -> Result<usize, Error> {
+    let pairs = CampCleanup::parse(input)?;
 
-    pairs.iter().filter(|p| p.is_fully_contained()).count()
+    Ok(CampCleanup::part_1(&pairs, &Params::default())?)
 }
 
-pub fn part_2(input: &str) -> usize {
-    let pairs: Vec<Pair> = input.lines().map(Pair::parse).collect();
+pub fn part_2(input: &str) -> Result<usize, Error> {
+    let pairs = CampCleanup::parse(input)?;
 
-    pairs.iter().filter(|p| p.is_overlapping()).count()
+    Ok(CampCleanup::part_2(&pairs, &Params::default())?)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    fn example_input_part_1() {
-        let input = include_str!("example.input");
-        let ans = part_1(input);
+    fn test_overlapping_none() {
+        let pair = parse_all("2-4,6-8", Pair::parse).unwrap();
 
-        assert_eq!(ans, 2);
+        assert!(!pair.is_fully_contained());
+        assert!(!pair.is_overlapping());
     }
 
     #[test]
-    fn my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = part_1(input);
+    fn parse_error_on_missing_range_end() {
+        let input = "2-4,6-8\n2-3,4\n";
+        let err = CampCleanup::parse(input).unwrap_err();
 
-        assert_eq!(ans, 444);
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.expected, "a range of sections like 2-4");
     }
 
     #[test]
-    fn example_input_part_2() {
-        let input = include_str!("example.input");
-        let ans = part_2(input);
+    fn parse_error_on_backwards_range() {
+        let input = "2-4,6-8\n4-2,3-5\n";
+        let err = CampCleanup::parse(input).unwrap_err();
 
-        assert_eq!(ans, 4);
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a range that ends after it starts");
     }
 
-    #[test]
-    fn test_overlapping_none() {
-        let pair = Pair::parse("2-4,6-8");
-
-        assert!(!pair.left_overlaps_right());
-        assert!(!pair.right_overlaps_left());
-        assert!(!pair.left_contains_right());
-        assert!(!pair.right_contains_left());
-        assert!(!pair.is_overlapping());
+    fn sections() -> impl Strategy<Value = Interval> {
+        (1..100i64, 0..20i64).prop_map(|(from, len)| Interval::new(from, from + len).unwrap())
     }
 
-    #[test]
-    fn my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = part_2(input);
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[0-9,\\-\\n]{0,64}") {
+            let _ = CampCleanup::parse(&input);
+        }
 
-        assert_eq!(ans, 801);
+        #[test]
+        fn contained_pairs_overlap(left in sections(), right in sections()) {
+            let pair = Pair { left, right };
+
+            prop_assert!(!pair.is_fully_contained() || pair.is_overlapping());
+        }
+
+        #[test]
+        fn overlapping_pairs_share_a_section(left in sections(), right in sections()) {
+            let shares_a_section = (left.start()..=left.end()).any(|s| right.contains(s));
+            let pair = Pair { left, right };
+            let swapped = Pair { left: right, right: left };
+
+            prop_assert_eq!(pair.is_overlapping(), shares_a_section);
+            prop_assert_eq!(swapped.is_overlapping(), shares_a_section);
+            prop_assert_eq!(pair.is_fully_contained(), swapped.is_fully_contained());
+        }
     }
 }
@@ -1,45 +1,86 @@
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_parse::{lines, parse_all, spanned, template, unsigned, until, Parsed, Spanned};
+
 type Stack = Vec<char>;
 
-struct CraneYard {
+#[derive(Debug)]
+pub struct CraneYard {
     stacks: Vec<Stack>,
     rearrangments: Vec<Rearrangments>,
 }
 
 impl CraneYard {
-    fn parse(input: &str) -> Self {
-        let mut split = input.split("\n\n");
-
-        let stacks = split.next().unwrap();
-        let rearrangments = split.next().unwrap();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (stacks, input) = until(
+            "\n\n",
+            "a blank line between the stacks and the rearrangements",
+        )(input)?;
 
         let mut to_parse: Vec<&str> = stacks.lines().collect();
-        let stack_ids = to_parse.pop().unwrap();
-        let stack_chars: Vec<char> = stack_ids.chars().collect();
+        let stack_ids = to_parse
+            .pop()
+            .ok_or_else(|| Unexpected::new(stacks, "a drawing of the stacks"))?;
 
-        let stack_count = stack_chars.chunks(4).len();
+        let stack_count = stack_columns(stack_ids).count();
         to_parse.reverse();
 
         let mut stacks: Vec<Stack> = vec![vec![]; stack_count];
 
         for s in to_parse.iter() {
-            let chars: Vec<char> = s.chars().collect();
-
-            for (i, chunk) in chars.chunks(4).enumerate() {
-                if chunk[0] != '[' {
+            for (i, column) in stack_columns(s).enumerate() {
+                if column.trim().is_empty() {
                     continue;
                 }
 
-                stacks[i].push(chunk[1])
+                let mut chars = column.chars();
+                let c = match (chars.next(), chars.next(), chars.next()) {
+                    (Some('['), Some(c), Some(']')) => c,
+                    _ => return Err(Unexpected::new(column.trim_end(), "a crate like [A]")),
+                };
+
+                stacks
+                    .get_mut(i)
+                    .ok_or_else(|| {
+                        Unexpected::new(column, format!("at most {stack_count} stacks"))
+                    })?
+                    .push(c)
             }
         }
 
-        Self {
-            rearrangments: rearrangments.lines().map(Rearrangments::parse).collect(),
-            stacks,
-        }
+        let (rearrangments, input) = lines(|line| Rearrangments::parse(line, stack_count))(input)?;
+
+        Ok((
+            Self {
+                stacks,
+                rearrangments,
+            },
+            input,
+        ))
     }
 }
 
+/// Splits a line of the stack drawing into the 4 character wide column
+/// each stack is drawn in
+fn stack_columns(line: &str) -> impl Iterator<Item = &str> {
+    let mut rest = line;
+
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let end = rest
+            .char_indices()
+            .nth(4)
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        let (column, remaining) = rest.split_at(end);
+        rest = remaining;
+
+        Some(column)
+    })
+}
+
 #[derive(PartialEq, Debug)]
 struct Rearrangments {
     from: usize,
@@ -48,79 +89,151 @@ struct Rearrangments {
 }
 
 impl Rearrangments {
-    fn parse(input: &str) -> Rearrangments {
-        let r = regex::Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
-
-        let captures = r
-            .captures(input)
-            .expect("Each rearrangement line should have this format");
+    fn parse(input: &str, stack_count: usize) -> Parsed<'_, Rearrangments> {
+        let ([count, from, to], rest) =
+            template("move {} from {} to {}", spanned(unsigned))(input)?;
+
+        Ok((
+            Self {
+                count: count.value,
+                from: Self::stack_index(from, stack_count)?,
+                to: Self::stack_index(to, stack_count)?,
+            },
+            rest,
+        ))
+    }
 
-        Self {
-            count: captures.get(1).unwrap().as_str().parse().unwrap(),
-            from: captures.get(2).unwrap().as_str().parse::<usize>().unwrap() - 1,
-            to: captures.get(3).unwrap().as_str().parse::<usize>().unwrap() - 1,
+    /// Stacks are numbered from 1 in the input, but we want to index from 0
+    fn stack_index(stack: Spanned<'_, usize>, stack_count: usize) -> Result<usize, Unexpected<'_>> {
+        if (1..=stack_count).contains(&stack.value) {
+            Ok(stack.value - 1)
+        } else {
+            Err(Unexpected::new(
+                stack.span,
+                format!("a stack from 1 to {stack_count}"),
+            ))
         }
     }
 }
 
-pub fn part_1(input: &str) -> String {
-    let mut yard = CraneYard::parse(input);
+pub struct SupplyStacks;
 
-    for r in yard.rearrangments {
-        for _ in 0..r.count {
-            let from_c = {
-                let from = yard.stacks.get_mut(r.from).unwrap();
-                from.pop().unwrap()
-            };
+impl Solution for SupplyStacks {
+    const DAY: u8 = 5;
+    const NAME: &'static str = "Supply Stacks";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
 
-            let to = yard.stacks.get_mut(r.to).unwrap();
+    type Input = CraneYard;
+    type Part1 = String;
+    type Part2 = String;
 
-            to.push(from_c);
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, CraneYard::parse).map_err(|e| e.locate(Self::DAY, input))
     }
 
-    yard.stacks.iter_mut().map(|s| s.pop().unwrap()).collect()
-}
+    fn part_1(yard: &Self::Input, _params: &Params) -> Result<String, SolveError> {
+        let mut stacks = yard.stacks.clone();
 
-pub fn part_2(input: &str) -> String {
-    let mut yard = CraneYard::parse(input);
+        for (step, r) in yard.rearrangments.iter().enumerate() {
+            for _ in 0..r.count {
+                let from_c = take_crate(&mut stacks, r.from, step)?;
 
-    for r in yard.rearrangments {
-        let mut holding_ground = vec![];
-        for _ in 0..r.count {
-            let from_c = {
-                let from = yard.stacks.get_mut(r.from).unwrap();
-                from.pop().unwrap()
-            };
+                let to = stacks.get_mut(r.to).unwrap();
 
-            holding_ground.push(from_c);
+                to.push(from_c);
+            }
         }
 
-        holding_ground.reverse();
+        tops(&stacks)
+    }
+
+    fn part_2(yard: &Self::Input, _params: &Params) -> Result<String, SolveError> {
+        let mut stacks = yard.stacks.clone();
+
+        for (step, r) in yard.rearrangments.iter().enumerate() {
+            let mut holding_ground = vec![];
+            for _ in 0..r.count {
+                let from_c = take_crate(&mut stacks, r.from, step)?;
+
+                holding_ground.push(from_c);
+            }
+
+            holding_ground.reverse();
+
+            let to = stacks.get_mut(r.to).unwrap();
+            to.append(&mut holding_ground);
+        }
 
-        let to = yard.stacks.get_mut(r.to).unwrap();
-        to.append(&mut holding_ground);
+        tops(&stacks)
     }
+}
+
+/// Takes the top crate off stack `from` during the `step`th rearrangement
+fn take_crate(stacks: &mut [Stack], from: usize, step: usize) -> Result<char, SolveError> {
+    stacks[from].pop().ok_or_else(|| {
+        SolveError::NoAnswer(format!(
+            "rearrangement {} takes a crate from stack {}, which is empty",
+            step + 1,
+            from + 1
+        ))
+    })
+}
+
+/// The crate on top of each stack, which spells out the answer
+fn tops(stacks: &[Stack]) -> Result<String, SolveError> {
+    stacks
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| {
+            stack.last().copied().ok_or_else(|| {
+                SolveError::NoAnswer(format!("stack {} ends up with no crates", i + 1))
+            })
+        })
+        .collect()
+}
 
-    yard.stacks.iter_mut().map(|s| s.pop().unwrap()).collect()
+pub fn part_1(input: &str) -> Result<String, Error> {
+    let yard = SupplyStacks::parse(input)?;
+
+    Ok(SupplyStacks::part_1(&yard, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<String, Error> {
+    let yard = SupplyStacks::parse(input)?;
+
+    Ok(SupplyStacks::part_2(&yard, &Params::default())?)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    fn example_input_part_1() {
-        let input = include_str!("example.input");
-        let ans = part_1(input);
-
-        assert_eq!(ans, "CMZ");
+    fn errors_instead_of_panicking() {
+        let too_many = SupplyStacks::parse("[A]    \n 1   2 \n\nmove 2 from 1 to 2\n").unwrap();
+        let emptied = SupplyStacks::parse("[A]    \n 1   2 \n\nmove 1 from 1 to 2\n").unwrap();
+
+        for yard in [too_many, emptied] {
+            assert!(matches!(
+                SupplyStacks::part_1(&yard, &Params::default()),
+                Err(SolveError::NoAnswer(_))
+            ));
+            assert!(matches!(
+                SupplyStacks::part_2(&yard, &Params::default()),
+                Err(SolveError::NoAnswer(_))
+            ));
+        }
     }
 
     #[test]
     fn test_parsing_rearrangments() {
         let input = include_str!("example.input");
-        let yard = CraneYard::parse(input);
+        let yard = parse_all(input, CraneYard::parse).unwrap();
 
         assert_eq!(yard.rearrangments.len(), 4);
         assert_eq!(
@@ -160,7 +273,7 @@ mod tests {
     #[test]
     fn test_parsing_stacks() {
         let input = include_str!("example.input");
-        let yard = CraneYard::parse(input);
+        let yard = parse_all(input, CraneYard::parse).unwrap();
 
         assert_eq!(yard.stacks.len(), 3);
 
@@ -170,26 +283,20 @@ mod tests {
     }
 
     #[test]
-    fn my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = part_1(input);
-
-        assert_eq!(ans, "QPJPLMNNR");
+    fn parse_error_on_stack_that_does_not_exist() {
+        let input =
+            include_str!("example.input").replace("move 1 from 1 to 2", "move 1 from 1 to 4");
+        let err = SupplyStacks::parse(&input).unwrap_err();
+
+        assert_eq!((err.line, err.column), (9, 18));
+        assert_eq!(err.text, "4");
+        assert_eq!(err.expected, "a stack from 1 to 3");
     }
 
-    #[test]
-    fn example_input_part_2() {
-        let input = include_str!("example.input");
-        let ans = part_2(input);
-
-        assert_eq!(ans, "MCD");
-    }
-
-    #[test]
-    fn my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = part_2(input);
-
-        assert_eq!(ans, "BQDNWJPVJ");
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "(\\[[A-Z]\\]| |[0-9]|\\n|move |from |to |\\[|\\]){0,64}") {
+            let _ = SupplyStacks::parse(&input);
+        }
     }
 }
@@ -1,53 +1,124 @@
 use std::collections::HashSet;
 
-pub fn end_of_packet_indicator<const WINDOW_SIZE: usize>(input: &str) -> usize {
-    let chars: Vec<_> = input.chars().collect();
+use aoc_core::{Fixture, Params, ParseError, Solution, SolveError};
+use aoc_parse::{chars, complete, label, parse_all};
+
+pub struct TuningTrouble;
+
+impl Solution for TuningTrouble {
+    const DAY: u8 = 6;
+    const NAME: &'static str = "Tuning Trouble";
+    const PARAMS: &'static [&'static str] = &["window_size"];
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
+
+    type Input = Vec<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    /// The datastream is a single line of lowercase letters
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let stream = input.strip_suffix('\n').unwrap_or(input);
+        let letters = complete(chars("a letter", |c| c.is_ascii_lowercase()));
+
+        parse_all(stream, label(letters, "a letter from a-z in a single line"))
+            .map(|letters| letters.chars().collect())
+            .map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    /// Start-of-packet markers are 4 distinct characters
+    fn part_1(chars: &Self::Input, params: &Params) -> Result<usize, SolveError> {
+        find_marker(chars, params, 4)
+    }
+
+    /// Start-of-message markers are 14 distinct characters
+    fn part_2(chars: &Self::Input, params: &Params) -> Result<usize, SolveError> {
+        find_marker(chars, params, 14)
+    }
+}
+
+/// There are only 26 letters to go round, so no bigger window can ever be a
+/// marker
+const MAX_WINDOW_SIZE: i64 = 26;
+
+fn find_marker(chars: &[char], params: &Params, window_size: i64) -> Result<usize, SolveError> {
+    let window_size = params.get_within("window_size", window_size, 1..=MAX_WINDOW_SIZE)?;
+
+    marker_position(chars, window_size as usize).ok_or_else(|| {
+        SolveError::NoAnswer(format!(
+            "no {window_size} characters in a row are all different"
+        ))
+    })
+}
+
+fn marker_position(chars: &[char], window_size: usize) -> Option<usize> {
     chars
-        .windows(WINDOW_SIZE)
-        .enumerate()
-        .find(|(_, window)| {
+        .windows(window_size)
+        .position(|window| {
             let hash_set: HashSet<_> = window.iter().collect();
 
             hash_set.len() == window.len()
         })
-        .unwrap()
-        .0
-        + WINDOW_SIZE
+        .map(|start| start + window_size)
+}
+
+/// Where the first marker of `window_size` distinct characters ends, if
+/// there is one. An empty window never counts as a marker
+pub fn end_of_packet_indicator(input: &str, window_size: usize) -> Option<usize> {
+    let chars: Vec<_> = input.chars().collect();
+
+    (window_size > 0)
+        .then(|| marker_position(&chars, window_size))
+        .flatten()
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    fn example_1_input_part_1() {
-        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-        let ans = end_of_packet_indicator::<4>(input);
+    fn parse_error_on_a_second_line() {
+        let err = TuningTrouble::parse("abcd\nefgh\n").unwrap_err();
 
-        assert_eq!(ans, 7);
+        assert_eq!((err.line, err.column), (1, 5));
     }
 
     #[test]
-    fn my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = end_of_packet_indicator::<4>(input);
+    fn finds_the_example_markers() {
+        let input = TuningTrouble::parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n").unwrap();
 
-        assert_eq!(ans, 1531);
+        assert_eq!(TuningTrouble::part_1(&input, &Params::default()), Ok(7));
+        assert_eq!(TuningTrouble::part_2(&input, &Params::default()), Ok(19));
     }
 
     #[test]
-    fn example_1_input_part_2() {
-        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-        let ans = end_of_packet_indicator::<14>(input);
+    fn errors_instead_of_panicking() {
+        let input = TuningTrouble::parse("aaaaaaaa\n").unwrap();
 
-        assert_eq!(ans, 19);
-    }
+        assert!(matches!(
+            TuningTrouble::part_1(&input, &Params::default()),
+            Err(SolveError::NoAnswer(_))
+        ));
+        for window_size in [0, -1, 27, i64::MAX] {
+            let params = Params::new().with("window_size", window_size);
 
-    #[test]
-    fn my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = end_of_packet_indicator::<14>(input);
+            assert!(matches!(
+                TuningTrouble::part_1(&input, &params),
+                Err(SolveError::BadParam { .. })
+            ));
+        }
+        assert_eq!(end_of_packet_indicator("aaaa", 0), None);
+        assert_eq!(end_of_packet_indicator("aaaa", 2), None);
+    }
 
-        assert_eq!(ans, 2518);
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[a-z\\n]{0,64}") {
+            let _ = TuningTrouble::parse(&input);
+        }
     }
 }
@@ -1,5 +1,8 @@
 use std::{collections::HashMap, path::PathBuf};
 
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError};
+use aoc_parse::{label, lines, one_of, parse_all, tag, unsigned, word, Parsed};
+
 #[derive(Debug)]
 enum FileLike {
     Dir { path: PathBuf },
@@ -23,55 +26,79 @@ impl FileLike {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum Command {
+    Cd,
+    Ls,
+}
+
+const COMMANDS: &[(&str, Command)] = &[("cd ", Command::Cd), ("ls", Command::Ls)];
+
+/// One line of the terminal, either a command we typed or what `ls` printed
+#[derive(Debug)]
+enum TerminalLine<'a> {
+    Cd(&'a str),
+    Ls,
+    Dir(&'a str),
+    File { size: usize, name: &'a str },
+}
+
+impl<'a> TerminalLine<'a> {
+    fn parse(line: &'a str) -> Parsed<'a, Self> {
+        if let Ok((_, command)) = tag("$ ")(line) {
+            match one_of(COMMANDS, "a cd or ls command")(command)? {
+                (Command::Cd, location) => {
+                    let (location, rest) = word(location)?;
+                    Ok((Self::Cd(location), rest))
+                }
+                (Command::Ls, rest) => Ok((Self::Ls, rest)),
+            }
+        } else if let Ok((_, name)) = tag("dir ")(line) {
+            let (name, rest) = word(name)?;
+            Ok((Self::Dir(name), rest))
+        } else {
+            let (size, rest) = label(unsigned, "a file size or dir")(line)?;
+            let (_, rest) = tag(" ")(rest)?;
+            let (name, rest) = word(rest)?;
+            Ok((Self::File { size, name }, rest))
+        }
+    }
+}
+
 #[derive(Debug)]
-struct FileSystem {
+pub struct FileSystem {
     files: HashMap<PathBuf, FileLike>,
 }
 
 impl FileSystem {
-    fn parse(input: &str) -> Self {
-        let mut files = HashMap::new();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (terminal, rest) = lines(TerminalLine::parse)(input)?;
 
+        let mut files = HashMap::new();
         let mut current_directory: PathBuf = "/".into();
 
-        for line in input.lines() {
-            if let Some(command) = line.strip_prefix("$ ") {
-                if let Some(cd_location) = command.strip_prefix("cd ") {
-                    if cd_location == "/" {
-                        current_directory = "/".into();
-                    } else if cd_location == ".." {
-                        current_directory.pop();
-                    } else {
-                        current_directory.push(cd_location);
-                    }
-                } else if command == "ls" {
-                    // We don't need to do anything here,
-                    // but we know we can read until the next command
+        for line in terminal {
+            match line {
+                TerminalLine::Cd("/") => current_directory = "/".into(),
+                TerminalLine::Cd("..") => {
+                    current_directory.pop();
+                }
+                TerminalLine::Cd(location) => current_directory.push(location),
+                // We don't need to do anything here,
+                // but we know we can read until the next command
+                TerminalLine::Ls => {}
+                TerminalLine::Dir(name) => {
+                    let path = current_directory.join(name);
+                    files.insert(path.clone(), FileLike::Dir { path });
+                }
+                TerminalLine::File { size, name } => {
+                    let path = current_directory.join(name);
+                    files.insert(path.clone(), FileLike::File { path, size });
                 }
-            } else {
-                let mut split = line.split(' ');
-
-                let file_size_or_dir = split.next().unwrap();
-                let path = split.next().unwrap();
-
-                let mut new_file_path = current_directory.clone();
-                new_file_path.push(path);
-
-                let filelike: FileLike = match file_size_or_dir {
-                    "dir" => FileLike::Dir {
-                        path: new_file_path.clone(),
-                    },
-                    size => FileLike::File {
-                        path: new_file_path.clone(),
-                        size: size.parse().unwrap(),
-                    },
-                };
-
-                files.insert(new_file_path, filelike);
             }
         }
 
-        Self { files }
+        Ok((Self { files }, rest))
     }
 
     fn total_size(&self, filelike: &FileLike) -> usize {
@@ -93,78 +120,121 @@ impl FileSystem {
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    let filesystem = FileSystem::parse(input);
+pub struct NoSpaceLeftOnDevice;
 
-    filesystem
-        .files
-        .values()
-        .filter(|f| matches!(f, FileLike::Dir { .. }))
-        .filter(|dir| filesystem.total_size(dir) < 100_000)
-        .map(|dir| filesystem.total_size(dir))
-        .sum()
-}
+impl Solution for NoSpaceLeftOnDevice {
+    const DAY: u8 = 7;
+    const NAME: &'static str = "No Space Left On Device";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
 
-pub fn part_2(input: &str) -> usize {
-    let filesystem = FileSystem::parse(input);
+    type Input = FileSystem;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let root = FileLike::Dir { path: "/".into() };
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, FileSystem::parse).map_err(|e| e.locate(Self::DAY, input))
+    }
 
-    const TOTAL_SPACE: usize = 70_000_000;
-    const SPACE_NEEDED: usize = 30_000_000;
+    fn part_1(filesystem: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        Ok(filesystem
+            .files
+            .values()
+            .filter(|f| matches!(f, FileLike::Dir { .. }))
+            .filter(|dir| filesystem.total_size(dir) < 100_000)
+            .map(|dir| filesystem.total_size(dir))
+            .sum())
+    }
 
-    let used_space = filesystem.total_size(&root);
-    let space_available = TOTAL_SPACE - used_space;
+    fn part_2(filesystem: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        let root = FileLike::Dir { path: "/".into() };
+
+        const TOTAL_SPACE: usize = 70_000_000;
+        const SPACE_NEEDED: usize = 30_000_000;
+
+        let used_space = filesystem.total_size(&root);
+        let space_available = TOTAL_SPACE.checked_sub(used_space).ok_or_else(|| {
+            SolveError::NoAnswer(format!(
+                "{used_space} is used, which doesn't fit on a {TOTAL_SPACE} disk"
+            ))
+        })?;
+
+        let space_needed_to_delete = SPACE_NEEDED
+            .checked_sub(space_available)
+            .filter(|&needed| needed > 0)
+            .ok_or_else(|| {
+                SolveError::NoAnswer(format!(
+                    "{space_available} is already free, so nothing needs deleting"
+                ))
+            })?;
+
+        filesystem
+            .files
+            .values()
+            .filter(|f| matches!(f, FileLike::Dir { .. }))
+            .filter(|dir| filesystem.total_size(dir) > space_needed_to_delete)
+            .map(|dir| filesystem.total_size(dir))
+            .min()
+            .ok_or_else(|| {
+                SolveError::NoAnswer(format!(
+                    "no directory frees up the {space_needed_to_delete} needed"
+                ))
+            })
+    }
+}
 
-    let space_needed_to_delete = if space_available > SPACE_NEEDED {
-        panic!("We don't need to delete anything");
-    } else {
-        SPACE_NEEDED - space_available
-    };
+pub fn part_1(input: &str) -> Result<usize, Error> {
+    let filesystem = NoSpaceLeftOnDevice::parse(input)?;
 
-    filesystem
-        .files
-        .values()
-        .filter(|f| matches!(f, FileLike::Dir { .. }))
-        .filter(|dir| filesystem.total_size(dir) > space_needed_to_delete)
-        .map(|dir| filesystem.total_size(dir))
-        .min()
-        .unwrap()
+    Ok(NoSpaceLeftOnDevice::part_1(
+        &filesystem,
+        &Params::default(),
+    )?)
+}
+
+pub fn part_2(input: &str) -> Result<usize, Error> {
+    let filesystem = NoSpaceLeftOnDevice::parse(input)?;
+
+    Ok(NoSpaceLeftOnDevice::part_2(
+        &filesystem,
+        &Params::default(),
+    )?)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use proptest::prelude::*;
 
-    #[test]
-    fn example_input_part_1() {
-        let input = include_str!("example.input");
-        let ans = part_1(input);
-
-        assert_eq!(ans, 95437);
-    }
+    use super::*;
 
     #[test]
-    fn my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = part_1(input);
-
-        assert_eq!(ans, 1086293);
+    fn errors_instead_of_panicking() {
+        let overfull = NoSpaceLeftOnDevice::parse("$ cd /\n$ ls\n70000001 a\n").unwrap();
+        let roomy = NoSpaceLeftOnDevice::parse("$ cd /\n$ ls\n5 a\n").unwrap();
+
+        for filesystem in [overfull, roomy] {
+            assert!(matches!(
+                NoSpaceLeftOnDevice::part_2(&filesystem, &Params::default()),
+                Err(SolveError::NoAnswer(_))
+            ));
+        }
     }
 
     #[test]
-    fn example_input_part_2() {
-        let input = include_str!("example.input");
-        let ans = part_2(input);
+    fn parse_error_on_unknown_command() {
+        let input = "$ cd /\n$ rm -rf /\n";
+        let err = NoSpaceLeftOnDevice::parse(input).unwrap_err();
 
-        assert_eq!(ans, 24933642);
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "a cd or ls command");
     }
 
-    #[test]
-    fn my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = part_2(input);
-
-        assert_eq!(ans, 366028);
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "(\\$ cd |\\$ ls|\\$ |dir |[a-z0-9]|/|\\.\\.| |\\n){0,64}") {
+            let _ = NoSpaceLeftOnDevice::parse(&input);
+        }
     }
 }
@@ -1,28 +1,16 @@
-use std::{collections::HashSet, hash::Hash};
-
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+use std::collections::HashSet;
 
-impl Direction {
-    fn parse(dir: &str) -> Self {
-        match dir {
-            "R" => Direction::Right,
-            "L" => Direction::Left,
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            _ => panic!("Unknown direction"),
-        }
-    }
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError};
+use aoc_grid::{Bounds, Coord, Direction};
+use aoc_parse::{lines, one_of, parse_all, tag, unsigned, Parsed};
+use aoc_vis::{Animation, Image, Pixel, Visualise};
 
-    pub(crate) fn all() -> [Direction; 4] {
-        [Self::Up, Self::Down, Self::Right, Self::Left]
-    }
-}
+const DIRECTIONS: &[(&str, Direction)] = &[
+    ("R", Direction::Right),
+    ("L", Direction::Left),
+    ("U", Direction::Up),
+    ("D", Direction::Down),
+];
 
 #[derive(Debug)]
 struct Movement {
@@ -31,124 +19,191 @@ struct Movement {
 }
 
 impl Movement {
-    fn parse(input: &str) -> Self {
-        let mut parts = input.split(' ');
-
-        let dir = parts.next().unwrap();
-        let dir = Direction::parse(dir);
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (dir, input) = one_of(DIRECTIONS, "one of R, L, U or D")(input)?;
+        let (_, input) = tag(" ")(input)?;
+        let (count, input) = unsigned(input)?;
 
-        let count = parts.next().unwrap();
-        let count = count.parse().unwrap();
-
-        Self { dir, count }
+        Ok((Self { dir, count }, input))
     }
 }
 
 #[derive(Debug)]
-struct PuzzleInput(Vec<Movement>);
+pub struct PuzzleInput(Vec<Movement>);
 
 impl PuzzleInput {
-    fn parse(input: &str) -> Self {
-        Self(input.lines().map(Movement::parse).collect())
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (movements, input) = lines(Movement::parse)(input)?;
+
+        Ok((Self(movements), input))
     }
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
-pub struct Coord {
-    x: isize,
-    y: isize,
-}
+pub struct RopeBridge;
 
-impl Coord {
-    fn move_in_dir(&self, dir: &Direction) -> Self {
-        match dir {
-            Direction::Up => Self {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::Down => Self {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Direction::Left => Self {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Direction::Right => Self {
-                x: self.x + 1,
-                y: self.y,
-            },
-        }
+impl Solution for RopeBridge {
+    const DAY: u8 = 9;
+    const NAME: &'static str = "Rope Bridge";
+    const PARAMS: &'static [&'static str] = &["tail_count"];
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("larger_example", include_str!("larger_example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
+
+    type Input = PuzzleInput;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, PuzzleInput::parse).map_err(|e| e.locate(Self::DAY, input))
     }
 
-    fn distance_to(&self, tail: &Coord) -> usize {
-        let x_diff = self.x.abs_diff(tail.x);
-        let y_diff = self.y.abs_diff(tail.y);
+    fn part_1(input: &Self::Input, params: &Params) -> Result<usize, SolveError> {
+        let tail_count = tail_count(params, 1)?;
 
-        x_diff.max(y_diff)
+        Ok(tail_positions(input, tail_count).len())
     }
 
-    fn needs_to_move_towards(&self, head: &Coord, d: &Direction) -> bool {
-        match d {
-            Direction::Up => head.y > self.y,
-            Direction::Down => head.y < self.y,
-            Direction::Left => head.x < self.x,
-            Direction::Right => head.x > self.x,
-        }
+    fn part_2(input: &Self::Input, params: &Params) -> Result<usize, SolveError> {
+        let tail_count = tail_count(params, 9)?;
+
+        Ok(tail_positions(input, tail_count).len())
     }
 }
 
-pub fn rope_course<const TAIL_COUNT: usize>(input: &str) -> HashSet<Coord>
-where
-    [Coord; TAIL_COUNT]: Default,
-{
-    let input = PuzzleInput::parse(input);
+/// Every knot moves on every step, so a rope much longer than this takes
+/// far too long to pull around
+const MAX_TAIL_COUNT: i64 = 1000;
+
+fn tail_count(params: &Params, default: i64) -> Result<usize, SolveError> {
+    let tail_count = params.get_within("tail_count", default, 1..=MAX_TAIL_COUNT)?;
+
+    Ok(tail_count as usize)
+}
+
+pub fn rope_course(input: &str, tails: usize) -> Result<HashSet<Coord>, Error> {
+    let params = Params::new().with("tail_count", tails.try_into().unwrap_or(i64::MAX));
+    let input = RopeBridge::parse(input)?;
+
+    Ok(tail_positions(&input, tail_count(&params, 1)?))
+}
+
+/// A rope made of knots, with the head first
+#[derive(Debug, Clone)]
+struct Rope {
+    knots: Vec<Coord>,
+}
+
+impl Rope {
+    fn new(tail_count: usize) -> Self {
+        assert!(tail_count > 0, "Must have at least one tail");
+
+        Self {
+            knots: vec![Coord::ORIGIN; tail_count + 1],
+        }
+    }
+
+    fn tail(&self) -> Coord {
+        *self.knots.last().unwrap()
+    }
 
-    let mut head: Coord = Default::default();
+    /// Moves the head one step, and drags the rest of the knots along behind
+    /// it
+    fn step(&mut self, dir: Direction) {
+        self.knots[0] = self.knots[0].step(dir);
+
+        for i in 1..self.knots.len() {
+            let (front, knot) = (self.knots[i - 1], self.knots[i]);
+
+            // Each knot follows the one in front of it by stepping
+            // straight, or diagonally, towards it
+            if front.chebyshev_distance(knot) > 1 {
+                self.knots[i] = knot + (front - knot).signum();
+            }
+        }
+    }
+}
 
-    assert!(TAIL_COUNT > 0, "Must have at least one tail");
-    let mut tails: [Coord; TAIL_COUNT] = Default::default();
+/// Returns every position the last tail of the rope visited
+fn tail_positions(input: &PuzzleInput, tail_count: usize) -> HashSet<Coord> {
+    let mut rope = Rope::new(tail_count);
 
     let mut last_tail_visited: HashSet<Coord> = Default::default();
-    last_tail_visited.insert(tails[0]);
+    last_tail_visited.insert(rope.tail());
 
-    for Movement { dir, count } in input.0 {
+    for &Movement { dir, count } in &input.0 {
         for _ in 0..count {
-            head = head.move_in_dir(&dir);
-
-            for tail_index in 0..TAIL_COUNT {
-                let current_head = if tail_index == 0 {
-                    head
-                } else {
-                    tails[tail_index - 1]
-                };
-
-                if current_head.distance_to(&tails[tail_index]) > 1 {
-                    for d in Direction::all() {
-                        if tails[tail_index].needs_to_move_towards(&current_head, &d) {
-                            tails[tail_index] = tails[tail_index].move_in_dir(&d);
-                        }
-                    }
-
-                    if tail_index == TAIL_COUNT - 1 {
-                        last_tail_visited.insert(tails[tail_index]);
-                    }
-                }
-            }
+            rope.step(dir);
+            last_tail_visited.insert(rope.tail());
         }
     }
 
     last_tail_visited
 }
 
+impl Visualise for RopeBridge {
+    /// Draws the rope after each movement, over the trail its tail leaves
+    /// behind
+    fn draw(
+        input: &Self::Input,
+        params: &Params,
+        animation: &mut Animation,
+    ) -> Result<(), SolveError> {
+        let tail_count = tail_count(params, 9)?;
+
+        // Every frame has to be the same size, so we need to know how far the
+        // rope gets first. The knots never stray outside where the head has
+        // been, since each one only ever steps towards the one in front
+        let mut rope = Rope::new(tail_count);
+        let mut bounds = Bounds::around(Coord::ORIGIN);
+        for &Movement { dir, count } in &input.0 {
+            for _ in 0..count {
+                rope.step(dir);
+                bounds.extend(rope.knots[0]);
+            }
+        }
+
+        // Shade 0 is the trail and shade 1 the knots behind the head
+        let mut rope = Rope::new(tail_count);
+        let mut trail = Image::new(bounds.width(), bounds.height(), 2);
+        let picture = |rope: &Rope, trail: &Image| {
+            let mut image = trail.clone();
+
+            for knot in rope.knots.iter().skip(1).rev() {
+                image.set(*knot - bounds.min, Pixel::Shade(1));
+            }
+            image.set(rope.knots[0] - bounds.min, Pixel::Highlight);
+
+            image
+        };
+
+        trail.set(rope.tail() - bounds.min, Pixel::Shade(0));
+        for &Movement { dir, count } in &input.0 {
+            for _ in 0..count {
+                rope.step(dir);
+                trail.set(rope.tail() - bounds.min, Pixel::Shade(0));
+            }
+
+            animation.step(|| picture(&rope, &trail));
+        }
+
+        animation.finish(trail);
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
     fn test_example_part_1() {
         let input = include_str!("example.input");
-        let ans = rope_course::<1>(input);
+        let ans = rope_course(input, 1).unwrap();
 
         let correct = [
             (0, 0),
@@ -167,52 +222,80 @@ mod tests {
         ];
         assert_eq!(correct.len(), 13);
 
+        // The puzzle draws y going up, but the grid's y goes down
         for (x, y) in correct {
-            let c = Coord { x, y };
+            let c = Coord::new(x, -y);
 
             assert!(ans.contains(&c), "Doesn't contain {c:?}");
         }
 
-        let correct: HashSet<_> = correct
-            .iter()
-            .map(|(x, y)| Coord { x: *x, y: *y })
-            .collect();
+        let correct: HashSet<_> = correct.iter().map(|(x, y)| Coord::new(*x, -*y)).collect();
         let diff: HashSet<_> = ans.difference(&correct).collect();
 
-        dbg!(diff);
-
-        assert_eq!(ans.len(), 13);
+        assert_eq!(ans.len(), 13, "Also visited {diff:?}");
     }
 
     #[test]
-    fn test_my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = rope_course::<1>(input);
+    fn parse_error_on_unknown_direction() {
+        let input = "R 4\nU 4\nX 3\n";
+        let err = RopeBridge::parse(input).unwrap_err();
 
-        assert_eq!(ans.len(), 6498);
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.expected, "one of R, L, U or D");
     }
 
     #[test]
-    fn test_example_nine_tail() {
-        let input = include_str!("example.input");
-        let ans = rope_course::<9>(input);
+    fn rejects_ropes_without_tails_or_with_too_many() {
+        let input = RopeBridge::parse(include_str!("example.input")).unwrap();
 
-        assert_eq!(ans.len(), 1);
+        for tail_count in [0, -1, MAX_TAIL_COUNT + 1, i64::MAX] {
+            let params = Params::new().with("tail_count", tail_count);
+
+            assert!(matches!(
+                RopeBridge::part_1(&input, &params),
+                Err(SolveError::BadParam { .. })
+            ));
+            assert!(RopeBridge::draw(&input, &params, &mut Animation::new()).is_err());
+        }
+        assert!(rope_course(include_str!("example.input"), 0).is_err());
     }
 
     #[test]
-    fn test_larger_example_nine_tail() {
-        let input = include_str!("larger_example.input");
-        let ans = rope_course::<9>(input);
-
-        assert_eq!(ans.len(), 36);
+    fn draws_the_tails_trail() {
+        let input = RopeBridge::parse(include_str!("larger_example.input")).unwrap();
+        let mut animation = Animation::new();
+        RopeBridge::draw(&input, &Params::default(), &mut animation).unwrap();
+
+        assert_eq!(animation.frames().len(), input.0.len() + 1);
+
+        let trail = animation.last().unwrap();
+        let visited = (0..trail.height() as isize)
+            .flat_map(|y| (0..trail.width() as isize).map(move |x| Coord::new(x, y)))
+            .filter(|c| trail.get(*c) == Some(Pixel::Shade(0)))
+            .count();
+        assert_eq!(visited, 36);
     }
 
-    #[test]
-    fn test_my_nine_tail() {
-        let input = include_str!("my.input");
-        let ans = rope_course::<9>(input);
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[RLUDX0-9 \\n-]{0,64}") {
+            let _ = RopeBridge::parse(&input);
+        }
+
+        #[test]
+        fn knots_stay_touching(
+            tail_count in 1..10usize,
+            steps in prop::collection::vec(prop::sample::select(Direction::ALL.to_vec()), 0..200),
+        ) {
+            let mut rope = Rope::new(tail_count);
 
-        assert_eq!(ans.len(), 2531);
+            for dir in steps {
+                rope.step(dir);
+
+                for pair in rope.knots.windows(2) {
+                    prop_assert!(pair[0].chebyshev_distance(pair[1]) <= 1, "{:?}", rope.knots);
+                }
+            }
+        }
     }
 }
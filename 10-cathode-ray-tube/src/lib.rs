@@ -1,27 +1,36 @@
-use std::collections::VecDeque;
+use aoc_core::{Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_grid::Coord;
+use aoc_parse::{label, lines, one_of, parse_all, signed, tag, Parsed};
+use aoc_vis::{Animation, Image, Pixel, Visualise};
 
 #[derive(Debug, Clone, Copy)]
-enum Command {
+pub enum Command {
     Addx(isize),
     Noop,
 }
 
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    Addx,
+    Noop,
+}
+
+const INSTRUCTIONS: &[(&str, Instruction)] =
+    &[("addx", Instruction::Addx), ("noop", Instruction::Noop)];
+
 impl Command {
-    fn parse(input: &str) -> Self {
-        let mut split = input.split(' ');
-        let command = split.next().unwrap();
-
-        match command {
-            "noop" => Command::Noop,
-            "addx" => {
-                let amount = split.next().unwrap().parse().unwrap();
-                Command::Addx(amount)
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        match one_of(INSTRUCTIONS, "addx or noop")(input)? {
+            (Instruction::Noop, rest) => Ok((Command::Noop, rest)),
+            (Instruction::Addx, rest) => {
+                let (_, rest) = label(tag(" "), "an amount to add")(rest)?;
+                let (amount, rest) = signed(rest)?;
+
+                Ok((Command::Addx(amount), rest))
             }
-            _ => panic!("Command {command:?} not implemented"),
         }
     }
 
-    #[allow(dead_code)]
     fn cycle_count(&self) -> isize {
         match self {
             Command::Noop => 1,
@@ -30,8 +39,36 @@ impl Command {
     }
 }
 
-pub fn be_a_cpu(input: &str) -> isize {
-    let mut commands: VecDeque<Command> = input.lines().map(Command::parse).collect();
+/// The program has to keep the CPU going for every pixel on the screen
+const CYCLES: isize = (SCREEN_WIDTH * SCREEN_HEIGHT) as isize;
+
+fn parse_program(input: &str) -> Parsed<'_, Vec<Command>> {
+    let (commands, rest) = lines(Command::parse)(input)?;
+    let cycles: isize = commands.iter().map(Command::cycle_count).sum();
+
+    if cycles < CYCLES {
+        let program = &input[..input.len() - rest.len()];
+
+        return Err(Unexpected::missing_after(
+            program.trim_end(),
+            format!("more instructions, the program only lasts {cycles} of the {CYCLES} cycles"),
+        ));
+    }
+
+    Ok((commands, rest))
+}
+
+/// Everything the CPU produced after running the whole program
+#[derive(Debug)]
+struct CpuOutput {
+    signal_strength: isize,
+    screen: String,
+    /// Where the middle of the sprite was during each cycle
+    sprite: Vec<isize>,
+}
+
+fn run_cpu(commands: &[Command]) -> CpuOutput {
+    let mut commands = commands.iter().copied();
 
     let mut x_register = 1;
     let mut cycle_count = 1;
@@ -43,6 +80,9 @@ pub fn be_a_cpu(input: &str) -> isize {
 
     let mut current_addx_command = None;
 
+    let mut screen = String::new();
+    let mut sprite = vec![];
+
     loop {
         // Starting a CPU Cycle
         let c = if let Some(current_command) = current_addx_command {
@@ -50,7 +90,9 @@ pub fn be_a_cpu(input: &str) -> isize {
 
             current_command
         } else {
-            let next_command = commands.pop_front().expect("We ran out of commands");
+            let next_command = commands
+                .next()
+                .expect("Parsing makes sure the program lasts every cycle");
 
             current_addx_command = if let Command::Addx(amount) = next_command {
                 Some(Command::Addx(amount))
@@ -74,18 +116,19 @@ pub fn be_a_cpu(input: &str) -> isize {
             current_important_cycle_index += 1;
         }
         // Draw Board
+        sprite.push(x_register);
         let m = (cycle_count - 1) % 40;
         if (x_register - 1..=x_register + 1).contains(&m) {
-            print!("#");
+            screen.push('#');
         } else {
-            print!(".");
-        }
-        if cycle_count % 40 == 0 {
-            println!();
+            screen.push('.');
         }
-        if cycle_count == 240 {
+        if cycle_count == CYCLES {
             break;
         }
+        if cycle_count % 40 == 0 {
+            screen.push('\n');
+        }
 
         // End of Cycle
         if let Command::Addx(amount) = c {
@@ -94,26 +137,148 @@ pub fn be_a_cpu(input: &str) -> isize {
         cycle_count += 1;
     }
 
-    important_signal_strenghts.iter().sum()
+    CpuOutput {
+        signal_strength: important_signal_strenghts.iter().sum(),
+        screen,
+        sprite,
+    }
+}
+
+pub struct CathodeRayTube;
+
+impl Solution for CathodeRayTube {
+    const DAY: u8 = 10;
+    const NAME: &'static str = "Cathode-Ray Tube";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
+
+    type Input = Vec<Command>;
+    type Part1 = isize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, parse_program).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(commands: &Self::Input, _params: &Params) -> Result<isize, SolveError> {
+        Ok(run_cpu(commands).signal_strength)
+    }
+
+    /// The answer is whatever capital letters show up on the CRT, so we
+    /// hand back the screen itself for someone to read
+    fn part_2(commands: &Self::Input, _params: &Params) -> Result<String, SolveError> {
+        Ok(run_cpu(commands).screen)
+    }
+}
+
+const SCREEN_WIDTH: usize = 40;
+const SCREEN_HEIGHT: usize = 6;
+
+impl Visualise for CathodeRayTube {
+    /// Draws the CRT one pixel per cycle, with the sprite showing on the row
+    /// being drawn and the beam picked out
+    fn draw(
+        commands: &Self::Input,
+        _params: &Params,
+        animation: &mut Animation,
+    ) -> Result<(), SolveError> {
+        let output = run_cpu(commands);
+        let lit: Vec<bool> = output
+            .screen
+            .chars()
+            .filter(|c| *c != '\n')
+            .map(|c| c == '#')
+            .collect();
+        let pixel = |i: usize| Coord::new((i % SCREEN_WIDTH) as isize, (i / SCREEN_WIDTH) as isize);
+
+        // Shade 0 is the sprite and shade 1 a lit pixel
+        let screen = |drawn: usize| {
+            let mut image = Image::new(SCREEN_WIDTH, SCREEN_HEIGHT, 2);
+
+            for (i, _) in lit.iter().enumerate().take(drawn).filter(|(_, lit)| **lit) {
+                image.set(pixel(i), Pixel::Shade(1));
+            }
+
+            image
+        };
+
+        for (cycle, sprite) in output.sprite.iter().enumerate() {
+            animation.step(|| {
+                let mut image = screen(cycle);
+                let beam = pixel(cycle);
+
+                for x in sprite - 1..=sprite + 1 {
+                    let c = Coord::new(x, beam.y);
+                    if image.get(c) == Some(Pixel::Background) {
+                        image.set(c, Pixel::Shade(0));
+                    }
+                }
+                image.set(beam, Pixel::Highlight);
+
+                image
+            });
+        }
+
+        animation.finish(screen(lit.len()));
+
+        Ok(())
+    }
+}
+
+pub fn be_a_cpu(input: &str) -> Result<isize, ParseError> {
+    let commands = CathodeRayTube::parse(input)?;
+    let output = run_cpu(&commands);
+
+    Ok(output.signal_strength)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    fn test_example_input_part_1() {
-        let input = include_str!("example.input");
-        let ans = be_a_cpu(input);
+    fn parse_error_on_addx_without_amount() {
+        let input = "noop\naddx\n";
+        let err = CathodeRayTube::parse(input).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.expected, "an amount to add");
+    }
+
+    #[test]
+    fn parse_error_on_a_program_too_short_to_draw_the_screen() {
+        let input = "noop\naddx 3\naddx -5\n";
+        let err = CathodeRayTube::parse(input).unwrap_err();
 
-        assert_eq!(ans, 13140);
+        assert_eq!((err.line, err.column), (3, 8));
+        assert_eq!(
+            err.expected,
+            "more instructions, the program only lasts 5 of the 240 cycles"
+        );
     }
 
     #[test]
-    fn test_my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = be_a_cpu(input);
+    fn draws_the_screen() {
+        let commands = CathodeRayTube::parse(include_str!("example.input")).unwrap();
+        let mut animation = Animation::new();
+        CathodeRayTube::draw(&commands, &Params::default(), &mut animation).unwrap();
+
+        assert_eq!(animation.frames().len(), 241);
 
-        assert_eq!(ans, 17940);
+        let screen = animation.last().unwrap();
+        let top_left: Vec<_> = (0..4).map(|x| screen.get(Coord::new(x, 0))).collect();
+        let (lit, dark) = (Some(Pixel::Shade(1)), Some(Pixel::Background));
+        assert_eq!(top_left, vec![lit, lit, dark, dark]);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "(noop|addx |-|[0-9]|\\n| ){0,64}") {
+            let _ = CathodeRayTube::parse(&input);
+        }
     }
 }
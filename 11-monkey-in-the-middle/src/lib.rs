@@ -1,5 +1,11 @@
 use std::ops::{Add, Div, Mul, Sub};
 
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_parse::{
+    blocks, label, one_of, parse_all, separated, spanned, tag, template, unsigned, Parsed, Spanned,
+};
+use tracing::debug;
+
 #[derive(Debug, Clone, PartialEq, Default)]
 struct WorryLevels(Vec<WorryLevel>);
 
@@ -10,7 +16,7 @@ struct WorryLevel {
 }
 impl WorryLevel {
     fn is_divisible(&self) -> bool {
-        self.current % self.divisor == 0
+        self.current.is_multiple_of(self.divisor)
     }
 }
 
@@ -106,15 +112,16 @@ enum BinaryOperator {
     Divide,
 }
 
+const OPERATORS: &[(&str, BinaryOperator)] = &[
+    ("+", BinaryOperator::Plus),
+    ("-", BinaryOperator::Minus),
+    ("*", BinaryOperator::Multiply),
+    ("/", BinaryOperator::Divide),
+];
+
 impl BinaryOperator {
-    fn parse(op: &str) -> BinaryOperator {
-        match op {
-            "+" => BinaryOperator::Plus,
-            "-" => BinaryOperator::Minus,
-            "*" => BinaryOperator::Multiply,
-            "/" => BinaryOperator::Divide,
-            _ => panic!("Operator unknown"),
-        }
+    fn parse(input: &str) -> Parsed<'_, BinaryOperator> {
+        one_of(OPERATORS, "one of +, -, * or /")(input)
     }
 
     fn run(&self, left: u64, right: u64) -> u64 {
@@ -134,11 +141,13 @@ enum OldOrNumber {
 }
 
 impl OldOrNumber {
-    fn parse(input: &str) -> OldOrNumber {
-        match input {
-            "old" => OldOrNumber::Old,
-            x => OldOrNumber::Number(x.parse().unwrap()),
+    fn parse(input: &str) -> Parsed<'_, OldOrNumber> {
+        if let Ok((_, rest)) = tag("old")(input) {
+            return Ok((OldOrNumber::Old, rest));
         }
+
+        let (number, rest) = label(unsigned, "old or a number")(input)?;
+        Ok((OldOrNumber::Number(number), rest))
     }
 
     fn apply(&self, old: u64) -> u64 {
@@ -157,22 +166,22 @@ struct Op {
 }
 
 impl Op {
-    fn parse(input: &str) -> Op {
-        let mut split = input.split(' ');
-
-        let left = split.next().unwrap();
-        let op = split.next().unwrap();
-        let right = split.next().unwrap();
-
-        let left = OldOrNumber::parse(left);
-        let right = OldOrNumber::parse(right);
-        let operator = BinaryOperator::parse(op);
-
-        Self {
-            left,
-            right,
-            operator,
-        }
+    /// An operation like `old * 19`
+    fn parse(input: &str) -> Parsed<'_, Op> {
+        let (left, input) = OldOrNumber::parse(input)?;
+        let (_, input) = tag(" ")(input)?;
+        let (operator, input) = BinaryOperator::parse(input)?;
+        let (_, input) = tag(" ")(input)?;
+        let (right, input) = OldOrNumber::parse(input)?;
+
+        Ok((
+            Self {
+                left,
+                right,
+                operator,
+            },
+            input,
+        ))
     }
 
     fn run<const WORRY_DIVISOR: u64>(&self, old: WorryLevels) -> WorryLevels {
@@ -248,57 +257,100 @@ struct InitialMonkey {
     divisor: u64,
 }
 
-impl InitialMonkey {
-    fn parse(input: &str) -> Self {
-        let regex = regex::Regex::new(
-            r"Monkey (?P<monkey_index>\d+):
-  Starting items: (?P<starting_items>.*)
-  Operation: new = (?P<op>.*)
-  Test: divisible by (?P<div>\d+)
-    If true: throw to monkey (?P<true_index>\d+)
-    If false: throw to monkey (?P<false_index>\d+)",
-        )
-        .unwrap();
-
-        let captures = regex.captures(input).unwrap();
-
-        let monkey_index = captures.name("monkey_index").unwrap();
-        let monkey_index = monkey_index.as_str().parse().unwrap();
-
-        let div = captures.name("div").unwrap();
-        let div = div.as_str().parse().unwrap();
-
-        let items = captures.name("starting_items").unwrap();
-        let items = items
-            .as_str()
-            .trim()
-            .split(", ")
-            .map(|x| x.parse().unwrap())
-            .collect();
+/// One monkey's block of the input as it was written. The numbers in it
+/// can only be checked once we know where it sits in the list and how many
+/// monkeys there are
+struct MonkeyNotes<'a> {
+    index: Spanned<'a, usize>,
+    items: Vec<u64>,
+    operation: Op,
+    divisor: Spanned<'a, u64>,
+    true_index: Spanned<'a, usize>,
+    false_index: Spanned<'a, usize>,
+}
 
-        let operation = captures.name("op").unwrap();
-        let operation = Op::parse(operation.as_str());
+impl<'a> MonkeyNotes<'a> {
+    fn parse(input: &'a str) -> Parsed<'a, Self> {
+        let next_line = label(tag("\n"), "the rest of the monkey's notes");
+
+        let ([index], input) = template("Monkey {}:", spanned(unsigned))(input)?;
+        let (_, input) = next_line(input)?;
+        let ([items], input) = template("  Starting items: {}", starting_items)(input)?;
+        let (_, input) = next_line(input)?;
+        let ([operation], input) = template("  Operation: new = {}", Op::parse)(input)?;
+        let (_, input) = next_line(input)?;
+        let ([divisor], input) = template("  Test: divisible by {}", spanned(unsigned))(input)?;
+        let (_, input) = next_line(input)?;
+        let ([true_index], input) =
+            template("    If true: throw to monkey {}", spanned(unsigned))(input)?;
+        let (_, input) = next_line(input)?;
+        let ([false_index], input) =
+            template("    If false: throw to monkey {}", spanned(unsigned))(input)?;
+
+        Ok((
+            Self {
+                index,
+                items,
+                operation,
+                divisor,
+                true_index,
+                false_index,
+            },
+            input,
+        ))
+    }
 
-        let true_index = captures.name("true_index").unwrap();
-        let true_index = true_index.as_str().parse().unwrap();
+    fn check(
+        self,
+        expected_index: usize,
+        monkey_count: usize,
+    ) -> Result<InitialMonkey, Unexpected<'a>> {
+        if self.index.value != expected_index {
+            return Err(Unexpected::new(
+                self.index.span,
+                format!("Monkey {expected_index} to come next"),
+            ));
+        }
+        if self.divisor.value == 0 {
+            return Err(Unexpected::new(self.divisor.span, "a divisor above 0"));
+        }
 
-        let false_index = captures.name("false_index").unwrap();
-        let false_index = false_index.as_str().parse().unwrap();
+        let monkey_target = |target: Spanned<'a, usize>| {
+            if target.value < monkey_count {
+                Ok(target.value)
+            } else {
+                Err(Unexpected::new(
+                    target.span,
+                    format!("a monkey from 0 to {}", monkey_count - 1),
+                ))
+            }
+        };
 
         let test = Test {
-            true_index,
-            false_index,
+            true_index: monkey_target(self.true_index)?,
+            false_index: monkey_target(self.false_index)?,
         };
 
-        Self {
-            index: monkey_index,
-            items,
-            operation,
+        Ok(InitialMonkey {
+            index: self.index.value,
+            items: self.items,
+            operation: self.operation,
             test,
-            divisor: div,
-        }
+            divisor: self.divisor.value,
+        })
+    }
+}
+
+/// The items a monkey starts with, which might be none at all
+fn starting_items(input: &str) -> Parsed<'_, Vec<u64>> {
+    if input.is_empty() {
+        Ok((vec![], input))
+    } else {
+        separated(unsigned, ", ")(input)
     }
+}
 
+impl InitialMonkey {
     fn into_monkey(self, monkies: &[InitialMonkey]) -> Monkey {
         let items = self
             .items
@@ -326,21 +378,28 @@ impl InitialMonkey {
     }
 }
 
-#[derive(Debug)]
-struct Forest {
+#[derive(Debug, Clone)]
+pub struct Forest {
     monkies: Vec<Monkey>,
 }
 
 impl Forest {
-    fn parse(input: &str) -> Self {
-        let monkies: Vec<_> = input.split("\n\n").map(InitialMonkey::parse).collect();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (notes, rest) = blocks(MonkeyNotes::parse)(input)?;
+
+        let monkey_count = notes.len();
+        let monkies: Vec<_> = notes
+            .into_iter()
+            .enumerate()
+            .map(|(i, notes)| notes.check(i, monkey_count))
+            .collect::<Result<_, _>>()?;
 
         let monkies = monkies
             .iter()
             .map(|im| im.clone().into_monkey(&monkies))
             .collect();
 
-        Self { monkies }
+        Ok((Self { monkies }, rest))
     }
 
     fn throw_items<const WORRY_DIVISOR: u64>(&mut self, midx: usize) {
@@ -368,76 +427,93 @@ impl Forest {
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    let mut parsed = Forest::parse(input);
+pub struct MonkeyInTheMiddle;
 
-    for _ in 0..20 {
-        parsed.round::<3>();
+impl Solution for MonkeyInTheMiddle {
+    const DAY: u8 = 11;
+    const NAME: &'static str = "Monkey in the Middle";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
+
+    type Input = Forest;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, Forest::parse).map_err(|e| e.locate(Self::DAY, input))
     }
 
-    parsed.monkies.sort_by_key(|m| m.inspection_count);
-    parsed.monkies.reverse();
+    fn part_1(forest: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        let mut parsed = forest.clone();
 
-    parsed.monkies[0..2]
-        .iter()
-        .map(|m| m.inspection_count)
-        .reduce(|accum, item| accum * item)
-        .unwrap()
-}
+        for _ in 0..20 {
+            parsed.round::<3>();
+        }
 
-pub fn part_2(input: &str) -> usize {
-    let mut parsed = Forest::parse(input);
+        parsed.monkies.sort_by_key(|m| m.inspection_count);
+        parsed.monkies.reverse();
 
-    for i in 0..10000 {
-        if i % 100 == 0 {
-            dbg!(i);
-        };
-        parsed.round::<1>();
+        Ok(parsed.monkies[0..2]
+            .iter()
+            .map(|m| m.inspection_count)
+            .reduce(|accum, item| accum * item)
+            .unwrap())
     }
 
-    parsed.monkies.sort_by_key(|m| m.inspection_count);
-    parsed.monkies.reverse();
+    fn part_2(forest: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        let mut parsed = forest.clone();
+
+        for round in 0..10000 {
+            if round % 1000 == 0 {
+                debug!(round, "Monkeys still throwing");
+            }
+            parsed.round::<1>();
+        }
+
+        parsed.monkies.sort_by_key(|m| m.inspection_count);
+        parsed.monkies.reverse();
 
-    parsed.monkies[0..2]
-        .iter()
-        .map(|m| m.inspection_count)
-        .reduce(|accum, item| accum * item)
-        .unwrap()
+        Ok(parsed.monkies[0..2]
+            .iter()
+            .map(|m| m.inspection_count)
+            .reduce(|accum, item| accum * item)
+            .unwrap())
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn part_1(input: &str) -> Result<usize, Error> {
+    let forest = MonkeyInTheMiddle::parse(input)?;
 
-    #[test]
-    fn test_example_input() {
-        let input = include_str!("example.input");
-        let ans = part_1(input);
+    Ok(MonkeyInTheMiddle::part_1(&forest, &Params::default())?)
+}
 
-        assert_eq!(ans, 10605);
-    }
+pub fn part_2(input: &str) -> Result<usize, Error> {
+    let forest = MonkeyInTheMiddle::parse(input)?;
 
-    #[test]
-    fn test_my_input() {
-        let input = include_str!("my.input");
-        let ans = part_1(input);
+    Ok(MonkeyInTheMiddle::part_2(&forest, &Params::default())?)
+}
 
-        assert_eq!(ans, 54752);
-    }
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
 
     #[test]
-    fn test_example_input_part_2() {
-        let input = include_str!("example.input");
-        let ans = part_2(input);
+    fn parse_error_on_misspelled_line() {
+        let input = include_str!("example.input").replacen("divisible", "divisable", 2);
+        let err = MonkeyInTheMiddle::parse(&input).unwrap_err();
 
-        assert_eq!(ans, 2713310158);
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.text, "  Test: divisable by 23");
     }
 
-    #[test]
-    fn test_my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = part_2(input);
-
-        assert_eq!(ans, 13606755504);
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "(Monkey |[0-9]|:|\\n|  Starting items: |, |  Operation: new = |old|[-+*/]| |  Test: divisible by |    If (true|false): throw to monkey ){0,64}") {
+            let _ = MonkeyInTheMiddle::parse(&input);
+        }
     }
 }
@@ -1,5 +1,10 @@
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_grid::{Coord, Grid};
+use aoc_parse::{grid, parse_all, spanned, Parsed};
+use aoc_vis::{Animation, Image, Palette, Pixel, Visualise};
+
 #[derive(Debug, Clone, Copy)]
 struct Hill(u8);
 
@@ -18,100 +23,23 @@ impl Hill {
 }
 
 #[derive(Debug, Clone)]
-struct MountainSide {
-    hills: Vec<Vec<Hill>>,
-}
-
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
+pub struct MountainSide {
+    hills: Grid<Hill>,
 }
 
-impl Dir {
-    const fn all() -> [Dir; 4] {
-        // [Dir::Up, Dir::Down, Dir::Left, Dir::Right]
-        [Dir::Down, Dir::Up, Dir::Right, Dir::Left]
-    }
-
-    fn from_move(from: Coord, to: Coord) -> Dir {
-        let x_diff = from.0 - to.0;
-        let y_diff = from.1 - to.1;
-
-        match (x_diff, y_diff) {
-            (-1, 0) => Dir::Right,
-            (0, 1) => Dir::Up,
-            (1, 0) => Dir::Left,
-            (0, -1) => Dir::Down,
-            _ => panic!("Impossible Move"),
-        }
-    }
-
-    fn char(&self) -> char {
-        match self {
-            Dir::Up => '^',
-            Dir::Down => 'v',
-            Dir::Left => '<',
-            Dir::Right => '>',
-        }
-    }
-}
 impl MountainSide {
     fn neighbors(&self, original: Coord) -> impl Iterator<Item = Coord> + '_ {
-        let climbable_height = self.get(original).height() + 1;
+        let climbable_height = self.hills[original].height() + 1;
 
-        Dir::all()
-            .into_iter()
-            .map(move |d| original.in_direction(&d))
-            .filter(|c| self.is_inbounds(*c))
-            .filter(move |x| climbable_height >= self.get(*x).height())
-    }
-
-    fn is_inbounds(&self, c: Coord) -> bool {
-        c.0 >= 0 && c.0 < self.width() && c.1 >= 0 && c.1 < self.height()
-    }
-
-    fn height(&self) -> isize {
-        self.hills.len() as isize
-    }
-
-    fn width(&self) -> isize {
-        self.hills[0].len() as isize
+        self.hills
+            .neighbours_4(original)
+            .filter(move |x| climbable_height >= self.hills[*x].height())
     }
 
     fn search_for(&self, needle: char) -> Option<Coord> {
         let needle = needle as u8;
 
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                let c = Coord(x, y);
-
-                if self.get(c).0 == needle {
-                    return Some(c);
-                }
-            }
-        }
-
-        None
-    }
-
-    fn all_coords(&self) -> Vec<Coord> {
-        let mut coords = Vec::with_capacity(self.width() as usize * self.height() as usize);
-
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                let c = Coord(x, y);
-
-                coords.push(c);
-            }
-        }
-
-        coords
-    }
-
-    fn get(&self, Coord(x, y): Coord) -> Hill {
-        self.hills[y as usize][x as usize]
+        self.hills.position(|hill| hill.0 == needle)
     }
 
     fn starting_pos(&self) -> Coord {
@@ -122,54 +50,11 @@ impl MountainSide {
         self.search_for('E').unwrap()
     }
 
-    #[allow(dead_code)]
-    fn print_path(&self, path: &[Coord]) {
-        let mut chars = HashMap::<Coord, char>::new();
-
-        let moves: Vec<Dir> = path
-            .windows(2)
-            .map(|window| -> Dir { Dir::from_move(window[0], window[1]) })
-            .collect();
-
-        let mut current = self.starting_pos();
-        for m in moves {
-            chars.insert(current, m.char());
-
-            current = current.in_direction(&m);
-        }
-
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                let coord = Coord(x, y);
-                let char = chars.get(&coord).unwrap_or(&'.');
-                print!("{char}")
-            }
-            println!()
-        }
-    }
-
     fn possible_starting_positions(&self) -> impl Iterator<Item = Coord> + '_ {
-        self.all_coords()
-            .into_iter()
-            .filter(|c| self.get(*c).is_possible_starting_pos())
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
-struct Coord(isize, isize);
-
-impl Coord {
-    fn in_direction(&self, d: &Dir) -> Coord {
-        match d {
-            Dir::Up => Coord(self.0, self.1 - 1),
-            Dir::Down => Coord(self.0, self.1 + 1),
-            Dir::Left => Coord(self.0 - 1, self.1),
-            Dir::Right => Coord(self.0 + 1, self.1),
-        }
-    }
-
-    fn manhattan_distance(&self, other: Coord) -> usize {
-        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+        self.hills
+            .iter()
+            .filter(|(_, hill)| hill.is_possible_starting_pos())
+            .map(|(c, _)| c)
     }
 }
 
@@ -182,9 +67,7 @@ struct ToSearch {
 
 impl PartialOrd for ToSearch {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        other
-            .estimated_distance
-            .partial_cmp(&self.estimated_distance)
+        Some(self.cmp(other))
     }
 }
 
@@ -201,16 +84,30 @@ struct PathFrom {
 }
 
 impl MountainSide {
-    fn parse(input: &str) -> MountainSide {
-        let hills = input
-            .lines()
-            .map(|l| l.chars().map(|c| Hill(c as u8)).collect())
-            .collect();
+    fn parse(input: &str) -> Parsed<'_, MountainSide> {
+        let (hills, rest) = spanned(grid("a height from a to z, or S or E", |c| match c {
+            'a'..='z' | 'S' | 'E' => Some(Hill(c as u8)),
+            _ => None,
+        }))(input)?;
+
+        let ms = Self { hills: hills.value };
+
+        for (needle, expected) in [('S', "a starting position S"), ('E', "a target position E")] {
+            if ms.search_for(needle).is_none() {
+                return Err(Unexpected::missing_after(hills.span.trim_end(), expected));
+            }
+        }
 
-        Self { hills }
+        Ok((ms, rest))
     }
 
     fn count_steps(&self, starting_pos: Coord, target_pos: Coord) -> Option<usize> {
+        self.shortest_path(starting_pos, target_pos)
+            .map(|path| path.len() - 1)
+    }
+
+    /// Every position along the shortest path, both ends included
+    fn shortest_path(&self, starting_pos: Coord, target_pos: Coord) -> Option<Vec<Coord>> {
         let mut paths_from = HashMap::<Coord, PathFrom>::new();
 
         let mut to_search = BinaryHeap::<ToSearch>::new();
@@ -258,9 +155,7 @@ impl MountainSide {
         let mut actual_path: Vec<Coord> = vec![target_pos];
         let mut curr: Coord = target_pos;
 
-        if paths_from.get(&target_pos).is_none() {
-            return None;
-        }
+        paths_from.get(&target_pos)?;
 
         while curr != starting_pos {
             let prev = paths_from.get(&curr).unwrap().from;
@@ -274,65 +169,103 @@ impl MountainSide {
         let path_set: HashSet<_> = actual_path.iter().collect();
         assert_eq!(path_set.len(), actual_path.len());
 
-        // self.print_path(&actual_path);
-
-        Some(actual_path.len() - 1)
+        Some(actual_path)
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    let ms: MountainSide = MountainSide::parse(input);
+pub struct HillClimbingAlgorithm;
 
-    ms.count_steps(ms.starting_pos(), ms.target_pos())
-        .expect("We expect there to always be a path for Part 1")
-}
+impl Solution for HillClimbingAlgorithm {
+    const DAY: u8 = 12;
+    const NAME: &'static str = "Hill Climbing Algorithm";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
 
-pub fn part_2(input: &str) -> usize {
-    let ms: MountainSide = MountainSide::parse(input);
+    type Input = MountainSide;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let target_pos = ms.target_pos();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, MountainSide::parse).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(ms: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        ms.count_steps(ms.starting_pos(), ms.target_pos())
+            .ok_or_else(|| SolveError::NoAnswer("there's no path from S to E".into()))
+    }
 
-    let starting_positions = ms.possible_starting_positions();
+    fn part_2(ms: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        let target_pos = ms.target_pos();
 
-    starting_positions
-        .filter_map(|c| ms.count_steps(c, target_pos))
-        .min()
-        .unwrap()
+        let starting_positions = ms.possible_starting_positions();
+
+        starting_positions
+            .filter_map(|c| ms.count_steps(c, target_pos))
+            .min()
+            .ok_or_else(|| SolveError::NoAnswer("there's no path from any a to E".into()))
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Visualise for HillClimbingAlgorithm {
+    const PALETTE: Palette = Palette::TERRAIN;
+
+    /// Draws the heightmap with a shade for each height, and walks the
+    /// shortest path from the start up to the target
+    fn draw(
+        ms: &Self::Input,
+        _params: &Params,
+        animation: &mut Animation,
+    ) -> Result<(), SolveError> {
+        let map = Image::from_grid(&ms.hills, 26, |hill| Pixel::Shade(hill.height() - b'a'));
+        let path = ms
+            .shortest_path(ms.starting_pos(), ms.target_pos())
+            .unwrap_or_default();
+
+        let mut image = map;
+        for c in path {
+            image.set(c, Pixel::Highlight);
+            animation.step(|| image.clone());
+        }
 
-    #[test]
-    fn example_input_part_1() {
-        let input = include_str!("example.input");
-        let ans = part_1(input);
+        animation.finish(image);
 
-        assert_eq!(ans, 31);
+        Ok(())
     }
+}
 
-    #[test]
-    fn my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = part_1(input);
+pub fn part_1(input: &str) -> Result<usize, Error> {
+    let ms = HillClimbingAlgorithm::parse(input)?;
 
-        assert_eq!(ans, 425);
-    }
+    Ok(HillClimbingAlgorithm::part_1(&ms, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<usize, Error> {
+    let ms = HillClimbingAlgorithm::parse(input)?;
+
+    Ok(HillClimbingAlgorithm::part_2(&ms, &Params::default())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
 
     #[test]
     fn to_search_end_example() {
         let input = include_str!("example.input");
-        let ms: MountainSide = MountainSide::parse(input);
+        let ms: MountainSide = parse_all(input, MountainSide::parse).unwrap();
 
-        assert_eq!(ms.target_pos(), Coord(5, 2));
-        assert_eq!(ms.starting_pos(), Coord(0, 0));
+        assert_eq!(ms.target_pos(), Coord::new(5, 2));
+        assert_eq!(ms.starting_pos(), Coord::new(0, 0));
     }
 
     #[test]
     fn example_starting_pos_has_neighbors() {
         let input = include_str!("example.input");
-        let ms: MountainSide = MountainSide::parse(input);
+        let ms: MountainSide = parse_all(input, MountainSide::parse).unwrap();
 
         assert_eq!(ms.neighbors(ms.starting_pos()).count(), 2);
     }
@@ -340,16 +273,16 @@ mod tests {
     #[test]
     fn my_input_to_search_end_example() {
         let input = include_str!("my.input");
-        let ms: MountainSide = MountainSide::parse(input);
+        let ms: MountainSide = parse_all(input, MountainSide::parse).unwrap();
 
-        assert_eq!(ms.starting_pos(), Coord(0, 20));
-        assert_eq!(ms.target_pos(), Coord(91, 20));
+        assert_eq!(ms.starting_pos(), Coord::new(0, 20));
+        assert_eq!(ms.target_pos(), Coord::new(91, 20));
     }
 
     #[test]
     fn my_starting_pos_has_neighbors() {
         let input = include_str!("my.input");
-        let ms: MountainSide = MountainSide::parse(input);
+        let ms: MountainSide = parse_all(input, MountainSide::parse).unwrap();
 
         assert_eq!(ms.neighbors(ms.starting_pos()).count(), 3);
     }
@@ -357,24 +290,52 @@ mod tests {
     #[test]
     fn my_35_19_has_neighbors() {
         let input = include_str!("my.input");
-        let ms: MountainSide = MountainSide::parse(input);
+        let ms: MountainSide = parse_all(input, MountainSide::parse).unwrap();
 
-        assert_eq!(ms.neighbors(Coord(35, 19)).count(), 4);
+        assert_eq!(ms.neighbors(Coord::new(35, 19)).count(), 4);
     }
 
     #[test]
-    fn example_input_part_2() {
-        let input = include_str!("example.input");
-        let ans = part_2(input);
+    fn errors_instead_of_panicking() {
+        let ms = HillClimbingAlgorithm::parse("SzE\n").unwrap();
+
+        assert!(matches!(
+            HillClimbingAlgorithm::part_1(&ms, &Params::default()),
+            Err(SolveError::NoAnswer(_))
+        ));
+        assert!(matches!(
+            HillClimbingAlgorithm::part_2(&ms, &Params::default()),
+            Err(SolveError::NoAnswer(_))
+        ));
+    }
+
+    #[test]
+    fn parse_error_on_unknown_height() {
+        let input = "Sab\nab!\nacE\n";
+        let err = HillClimbingAlgorithm::parse(input).unwrap_err();
 
-        assert_eq!(ans, 29);
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "!");
     }
 
     #[test]
-    fn my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = part_2(input);
+    fn draws_the_path_over_the_heights() {
+        let ms = HillClimbingAlgorithm::parse(include_str!("example.input")).unwrap();
+        let mut animation = Animation::new();
+        HillClimbingAlgorithm::draw(&ms, &Params::default(), &mut animation).unwrap();
+
+        // 31 steps visit 32 positions
+        assert_eq!(animation.frames().len(), 33);
 
-        assert_eq!(ans, 418);
+        let map = animation.last().unwrap();
+        assert_eq!(map.get(ms.target_pos()), Some(Pixel::Highlight));
+        assert_eq!(map.get(Coord::new(0, 4)), Some(Pixel::Shade(0)));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[a-zSE\\n]{0,64}") {
+            let _ = HillClimbingAlgorithm::parse(&input);
+        }
     }
 }
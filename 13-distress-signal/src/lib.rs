@@ -1,4 +1,5 @@
-use serde_json::Value;
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_parse::{blocks, label, parse_all, separated, tag, unsigned, Parsed};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct Packet {
@@ -6,40 +7,74 @@ struct Packet {
 }
 
 impl Packet {
-    fn parse(input: &str) -> Self {
-        let json: serde_json::Value = serde_json::from_str(input).unwrap();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (items, rest) = ListOrInteger::list(input, 0)?;
 
-        let items = json
-            .as_array()
-            .expect("The top level packet is always supposed to be an array")
-            .iter()
-            .map(ListOrInteger::from_json)
-            .collect();
-
-        Self { items }
+        Ok((Self { items }, rest))
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 enum ListOrInteger {
     List(Vec<ListOrInteger>),
     Integer(u64),
 }
 
+/// How deep lists can go before we give up rather than run out of stack
+const MAX_DEPTH: usize = 128;
+
 impl ListOrInteger {
-    fn from_json(input: &Value) -> Self {
-        match input {
-            Value::Number(x) => Self::Integer(x.as_u64().expect("Should always fit in a u64")),
-            Value::Array(a) => Self::List(a.iter().map(Self::from_json).collect()),
-            _ => panic!("Unsupported JSON"),
+    fn parse(input: &str, depth: usize) -> Parsed<'_, Self> {
+        if input.starts_with('[') {
+            let (items, rest) = Self::list(input, depth + 1)?;
+            Ok((Self::List(items), rest))
+        } else {
+            let (integer, rest) = label(unsigned, "a list or an integer")(input)?;
+            Ok((Self::Integer(integer), rest))
+        }
+    }
+
+    /// A list like `[1,[2,3]]`, which might be empty
+    fn list(input: &str, depth: usize) -> Parsed<'_, Vec<Self>> {
+        let (_, rest) = label(tag("["), "a list")(input)?;
+        if depth > MAX_DEPTH {
+            return Err(Unexpected::new(
+                &input[..1],
+                format!("lists nested less than {MAX_DEPTH} deep"),
+            ));
         }
+
+        let (items, rest) = if rest.starts_with(']') {
+            (vec![], rest)
+        } else {
+            separated(|item| Self::parse(item, depth), ",")(rest)?
+        };
+
+        let (_, rest) = label(tag("]"), "a comma or the end of the list")(rest)?;
+        Ok((items, rest))
     }
 }
 
+// An integer compares equal to a list holding just that integer, so equality
+// has to come from the ordering rather than be derived
+impl PartialEq for ListOrInteger {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for ListOrInteger {}
+
 impl PartialOrd for ListOrInteger {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ListOrInteger {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match (self, other) {
-            (Self::Integer(l), Self::Integer(r)) => l.partial_cmp(r),
+            (Self::Integer(l), Self::Integer(r)) => l.cmp(r),
             (Self::List(l), Self::List(r)) => {
                 let max_i = l.len().min(r.len());
 
@@ -47,119 +82,171 @@ impl PartialOrd for ListOrInteger {
                     let i_l = &l[i];
                     let i_r = &r[i];
 
-                    let res = i_l.partial_cmp(i_r).unwrap();
+                    let res = i_l.cmp(i_r);
 
                     match res {
                         std::cmp::Ordering::Equal => continue,
-                        x => return Some(x),
+                        x => return x,
                     }
                 }
 
-                Some(l.len().cmp(&r.len()))
+                l.len().cmp(&r.len())
             }
             (Self::Integer(l), Self::List(r)) => {
-                Self::List(vec![Self::Integer(*l)]).partial_cmp(&Self::List(r.clone()))
+                Self::List(vec![Self::Integer(*l)]).cmp(&Self::List(r.clone()))
             }
             (Self::List(l), Self::Integer(r)) => {
-                Self::List(l.clone()).partial_cmp(&Self::List(vec![Self::Integer(*r)]))
+                Self::List(l.clone()).cmp(&Self::List(vec![Self::Integer(*r)]))
             }
         }
     }
 }
 
-impl Ord for ListOrInteger {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
 #[derive(Debug, Clone, PartialEq)]
-struct PacketPair {
+pub struct PacketPair {
     left: Packet,
     right: Packet,
 }
 
 impl PacketPair {
-    fn parse(input: &str) -> Self {
-        let mut split = input.lines();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (left, input) = Packet::parse(input)?;
+        let (_, input) = label(tag("\n"), "a second packet")(input)?;
+        let (right, input) = Packet::parse(input)?;
+
+        Ok((Self { left, right }, input))
+    }
+}
+
+pub struct DistressSignal;
 
-        let left = split.next().unwrap();
-        let left = Packet::parse(left);
+impl Solution for DistressSignal {
+    const DAY: u8 = 13;
+    const NAME: &'static str = "Distress Signal";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
 
-        let right = split.next().unwrap();
-        let right = Packet::parse(right);
+    type Input = Vec<PacketPair>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-        Self { left, right }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, blocks(PacketPair::parse)).map_err(|e| e.locate(Self::DAY, input))
     }
-}
 
-pub fn part_1(input: &str) -> usize {
-    let packet_pairs: Vec<_> = input.split("\n\n").map(PacketPair::parse).collect();
-
-    packet_pairs
-        .iter()
-        .enumerate()
-        .filter_map(|(i, pair)| {
-            if pair.left < pair.right {
-                Some(i + 1)
-            } else {
-                None
-            }
-        })
-        .sum()
-}
+    fn part_1(packet_pairs: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        Ok(packet_pairs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pair)| {
+                if pair.left < pair.right {
+                    Some(i + 1)
+                } else {
+                    None
+                }
+            })
+            .sum())
+    }
 
-pub fn part_2(input: &str) -> usize {
-    let input = input.replace("\n\n", "\n");
-    let mut packets: Vec<_> = input.trim().split('\n').map(Packet::parse).collect();
+    fn part_2(packet_pairs: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        let mut packets: Vec<_> = packet_pairs
+            .iter()
+            .flat_map(|pair| [pair.left.clone(), pair.right.clone()])
+            .collect();
 
-    let first_divider = Packet::parse("[[2]]");
-    let second_divider = Packet::parse("[[6]]");
+        let first_divider = parse_all("[[2]]", Packet::parse).unwrap();
+        let second_divider = parse_all("[[6]]", Packet::parse).unwrap();
 
-    packets.push(first_divider.clone());
-    packets.push(second_divider.clone());
+        packets.push(first_divider.clone());
+        packets.push(second_divider.clone());
 
-    packets.sort();
+        packets.sort();
 
-    let first_idx = packets.iter().position(|x| &first_divider == x).unwrap() + 1;
-    let second_idx = packets.iter().position(|x| &second_divider == x).unwrap() + 1;
+        let first_idx = packets.iter().position(|x| &first_divider == x).unwrap() + 1;
+        let second_idx = packets.iter().position(|x| &second_divider == x).unwrap() + 1;
 
-    first_idx * second_idx
+        Ok(first_idx * second_idx)
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, Error> {
+    let packet_pairs = DistressSignal::parse(input)?;
+
+    Ok(DistressSignal::part_1(&packet_pairs, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<usize, Error> {
+    let packet_pairs = DistressSignal::parse(input)?;
+
+    Ok(DistressSignal::part_2(&packet_pairs, &Params::default())?)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    fn part_1_example_input() {
-        let input = include_str!("example.input");
-        let ans = part_1(input);
+    fn integers_equal_lists_holding_just_them() {
+        let integer = ListOrInteger::Integer(2);
+        let list = ListOrInteger::List(vec![ListOrInteger::List(vec![integer.clone()])]);
 
-        assert_eq!(ans, 13);
+        assert_eq!(integer.cmp(&list), Ordering::Equal);
+        assert_eq!(integer, list);
     }
 
-    #[test]
-    fn part_1_my_input() {
-        let input = include_str!("my.input");
-        let ans = part_1(input);
+    /// Small integers and short, shallow lists, so that packets often share a
+    /// prefix and the interesting comparisons come up
+    fn list_or_integer() -> impl Strategy<Value = ListOrInteger> {
+        let integer = (0..4u64).prop_map(ListOrInteger::Integer);
 
-        assert_eq!(ans, 5390);
+        integer.prop_recursive(4, 32, 4, |inner| {
+            prop::collection::vec(inner, 0..4).prop_map(ListOrInteger::List)
+        })
     }
 
     #[test]
-    fn part_2_example_input() {
-        let input = include_str!("example.input");
-        let ans = part_2(input);
+    fn parse_error_on_malformed_packet() {
+        let input = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4,,]\n";
+        let err = DistressSignal::parse(input).unwrap_err();
 
-        assert_eq!(ans, 140);
+        assert_eq!((err.line, err.column), (5, 8));
+        assert_eq!(err.text, ",]");
     }
 
-    #[test]
-    fn part_2_my_input() {
-        let input = include_str!("my.input");
-        let ans = part_2(input);
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[\\[\\],0-9\\n-]{0,64}") {
+            let _ = DistressSignal::parse(&input);
+        }
+
+        #[test]
+        fn ordering_is_antisymmetric(a in list_or_integer(), b in list_or_integer()) {
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        }
 
-        assert_eq!(ans, 19261);
+        #[test]
+        fn ordering_is_transitive(
+            a in list_or_integer(),
+            b in list_or_integer(),
+            c in list_or_integer(),
+        ) {
+            let mut sorted = [a, b, c];
+            sorted.sort();
+
+            prop_assert!(sorted[0] <= sorted[1] && sorted[1] <= sorted[2]);
+            prop_assert!(sorted[0] <= sorted[2]);
+        }
+
+        #[test]
+        fn ordering_agrees_with_equality(a in list_or_integer(), b in list_or_integer()) {
+            prop_assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+            prop_assert_eq!(&a, &a);
+        }
     }
 }
@@ -1,66 +1,50 @@
-use std::collections::HashSet;
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_grid::{Bounds, Coord, Direction, SparseGrid};
+use aoc_parse::{label, lines, parse_all, separated, signed, spanned, tag, Parsed};
+use aoc_vis::{Animation, Image, Pixel, Visualise};
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
-struct Coord(u64, u64);
+fn parse_point(input: &str) -> Parsed<'_, Coord> {
+    let (x, input) = signed(input)?;
+    let (_, input) = label(tag(","), "a point like 498,4")(input)?;
+    let (y, input) = signed(input)?;
 
-impl Coord {
-    fn parse(input: &str) -> Self {
-        let mut split = input.split(",").map(|x| x.parse().unwrap());
-
-        let x = split.next().unwrap();
-        let y = split.next().unwrap();
-
-        Self(x, y)
-    }
-
-    fn next_move(&self, frame: &Frame) -> Option<Coord> {
-        let rocks = &frame.rocks;
-        let down = self.down();
-        let down_left = down.left();
-        let down_right = down.right();
-
-        if frame.floor_is_solid && down.1 >= frame.floor {
-            return None;
-        }
-
-        if !rocks.contains(&down) {
-            Some(down)
-        } else if !rocks.contains(&down_left) {
-            Some(down_left)
-        } else if !rocks.contains(&down_right) {
-            Some(down_right)
-        } else {
-            None
-        }
-    }
-
-    fn down(&self) -> Coord {
-        Self(self.0, self.1 + 1)
-    }
-
-    fn left(&self) -> Coord {
-        Self(self.0 - 1, self.1)
-    }
+    Ok((Coord::new(x, y), input))
+}
 
-    fn right(&self) -> Coord {
-        Self(self.0 + 1, self.1)
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Material {
+    Rock,
+    Sand,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct RockStructure {
     vertices: Vec<Coord>,
 }
 
 impl RockStructure {
-    fn parse(input: &str) -> Self {
-        let vertices = input
-            .split("->")
-            .map(|x| x.trim())
-            .map(Coord::parse)
-            .collect();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (points, rest) = separated(spanned(parse_point), " -> ")(input)?;
 
-        Self { vertices }
+        // A path with one point in it doesn't lay down any rock
+        if let [only] = &points[..] {
+            return Err(Unexpected::missing_after(only.span, "-> and another point"));
+        }
+
+        // We can only draw straight lines between the vertices
+        for pair in points.windows(2) {
+            let (from, to) = (pair[0].value, pair[1].value);
+
+            if from.x != to.x && from.y != to.y {
+                return Err(Unexpected::new(
+                    pair[1].span,
+                    "a point in a straight line from the one before it",
+                ));
+            }
+        }
+
+        let vertices = points.into_iter().map(|p| p.value).collect();
+        Ok((Self { vertices }, rest))
     }
 
     fn rocks(&self) -> impl IntoIterator<Item = Coord> + '_ {
@@ -69,26 +53,26 @@ impl RockStructure {
             let start = a[0];
             let end = a[1];
 
-            let x_diff = start.0.abs_diff(end.0);
-            let y_diff = start.1.abs_diff(end.1);
+            let x_diff = start.x.abs_diff(end.x);
+            let y_diff = start.y.abs_diff(end.y);
 
             if x_diff != 0 && y_diff != 0 {
                 panic!("This line isn't straight and we don't support that")
             } else if x_diff == 0 {
                 // This means we have a vertical line
-                let s = start.1.min(end.1);
-                let e = start.1.max(end.1);
+                let s = start.y.min(end.y);
+                let e = start.y.max(end.y);
 
                 for i in s..=e {
-                    coords.push(Coord(start.0, i));
+                    coords.push(Coord::new(start.x, i));
                 }
             } else {
                 // This is a horizontal line
-                let s = start.0.min(end.0);
-                let e = start.0.max(end.0);
+                let s = start.x.min(end.x);
+                let e = start.x.max(end.x);
 
                 for i in s..=e {
-                    coords.push(Coord(i, start.1));
+                    coords.push(Coord::new(i, start.y));
                 }
             }
 
@@ -97,58 +81,133 @@ impl RockStructure {
     }
 }
 
-#[derive(Debug)]
-struct Maze {
+#[derive(Debug, Clone)]
+pub struct Maze {
     rocks: Vec<RockStructure>,
 }
 
 impl Maze {
-    fn parse(input: &str) -> Self {
-        let rocks = input.lines().map(RockStructure::parse).collect();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (rocks, rest) = lines(RockStructure::parse)(input)?;
+
+        if rocks.is_empty() {
+            return Err(Unexpected::new(input, "at least one path of rock"));
+        }
 
-        Self { rocks }
+        Ok((Self { rocks }, rest))
     }
 
-    /// Returns a HashSet of Coordinates that represent the starting rocks for the maze
-    /// built from the lines in the vertices
-    fn initial_rocks(&self) -> HashSet<Coord> {
-        self.rocks.iter().flat_map(|r| r.rocks()).collect()
+    /// Returns a grid of the starting rocks for the maze built from the lines
+    /// in the vertices
+    fn initial_rocks(&self) -> SparseGrid<Material> {
+        self.rocks
+            .iter()
+            .flat_map(|r| r.rocks())
+            .map(|c| (c, Material::Rock))
+            .collect()
     }
 }
 
-pub fn part_1(input: &str) -> u64 {
-    let parsed = Maze::parse(input);
+pub struct RegolithReservoir;
+
+impl Solution for RegolithReservoir {
+    const DAY: u8 = 14;
+    const NAME: &'static str = "Regolith Reservoir";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
 
-    let mut current: Frame = parsed.into_frame_without_floor();
-    let mut count = 0;
+    type Input = Maze;
+    type Part1 = u64;
+    type Part2 = u64;
 
-    while let Some(f) = current.next() {
-        current = f;
-        count += 1;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, Maze::parse).map_err(|e| e.locate(Self::DAY, input))
     }
 
-    count
-}
+    fn part_1(maze: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        let mut current: Frame = maze.clone().into_frame_without_floor();
+        let mut count = 0;
+
+        while let Some(f) = current.next() {
+            current = f;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    fn part_2(maze: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        let mut current: Frame = maze.clone().into_frame_with_floor(true);
+        let mut count = 0;
 
-pub fn part_2(input: &str) -> u64 {
-    let parsed = Maze::parse(input);
+        while let Some(f) = current.next() {
+            current = f;
+            count += 1;
+        }
 
-    let mut current: Frame = parsed.into_frame_with_floor(true);
-    let mut count = 0;
+        Ok(count)
+    }
+}
+
+impl Visualise for RegolithReservoir {
+    /// Drops the sand from part 1 a grain at a time, until it starts pouring
+    /// into the abyss
+    fn draw(
+        maze: &Self::Input,
+        _params: &Params,
+        animation: &mut Animation,
+    ) -> Result<(), SolveError> {
+        let mut frame = maze.clone().into_frame_without_floor();
+
+        // Sand only comes to rest on top of something, so it can't settle
+        // outside the rocks' bounds. We leave a column either side to show
+        // where it falls past them
+        let mut bounds = frame
+            .cave
+            .bounds()
+            .expect("Parsing makes sure there's always some rock");
+        bounds.extend(SAND_START);
+        bounds.extend(Coord::new(bounds.min.x - 1, bounds.max.y));
+        bounds.extend(Coord::new(bounds.max.x + 1, bounds.max.y));
+
+        // Working out the next frame uses up this one, so it's drawn first
+        loop {
+            let picture = frame.picture(bounds);
+
+            match frame.next() {
+                Some(next) => {
+                    animation.step(|| picture);
+                    frame = next;
+                }
+                None => {
+                    animation.finish(picture);
 
-    while let Some(f) = current.next() {
-        current = f;
-        count += 1;
+                    return Ok(());
+                }
+            }
+        }
     }
+}
 
-    count
+pub fn part_1(input: &str) -> Result<u64, Error> {
+    let maze = RegolithReservoir::parse(input)?;
+
+    Ok(RegolithReservoir::part_1(&maze, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<u64, Error> {
+    let maze = RegolithReservoir::parse(input)?;
+
+    Ok(RegolithReservoir::part_2(&maze, &Params::default())?)
 }
 
 #[derive(Debug)]
 struct Frame {
     turn: usize,
-    rocks: HashSet<Coord>,
-    floor: u64,
+    cave: SparseGrid<Material>,
+    floor: isize,
     floor_is_solid: bool,
 }
 
@@ -158,95 +217,135 @@ impl Maze {
     }
 
     fn into_frame_with_floor(self, is_solid: bool) -> Frame {
-        let rocks = self.initial_rocks();
-        let max_y = rocks.iter().map(|c| c.1).max().unwrap();
+        let cave = self.initial_rocks();
+        let max_y = cave
+            .bounds()
+            .expect("Parsing makes sure there's always some rock")
+            .max
+            .y;
         let floor = max_y + 2;
 
         Frame {
-            rocks,
+            cave,
             turn: 0,
-            floor: floor,
+            floor,
             floor_is_solid: is_solid,
         }
     }
 }
 
-const SAND_START: Coord = Coord(500, 0);
+const SAND_START: Coord = Coord::new(500, 0);
 
 impl Frame {
     fn next(self) -> Option<Frame> {
         let mut sand = SAND_START;
 
-        if self.rocks.contains(&SAND_START) {
+        if self.cave.contains(SAND_START) {
             return None;
         }
 
-        while let Some(s) = sand.next_move(&self) {
+        while let Some(s) = self.next_move(sand) {
             sand = s;
 
             // There is an infinite loop here if the sand falls forever
             // But if we can say for sure the sand is below all rocks,
             // we know it is going to keep falling and can be done
-            if sand.1 > self.floor {
-                assert_eq!(self.floor_is_solid, false, "We couldn't get here if the floor was solid, cause the sand would have stopped on the floor");
+            if sand.y > self.floor {
+                assert!(!self.floor_is_solid, "We couldn't get here if the floor was solid, cause the sand would have stopped on the floor");
 
                 return None;
             }
         }
 
-        let mut new_rocks = self.rocks;
-        new_rocks.insert(sand);
+        let mut new_cave = self.cave;
+        new_cave.insert(sand, Material::Sand);
 
         Some(Frame {
             turn: self.turn + 1,
-            rocks: new_rocks,
+            cave: new_cave,
             floor: self.floor,
             floor_is_solid: self.floor_is_solid,
         })
     }
+
+    /// Shade 0 is rock and shade 1 sand, with the hole the sand pours out of
+    /// picked out
+    fn picture(&self, bounds: Bounds) -> Image {
+        Image::from_bounds(bounds, 2, |c| match self.cave.get(c) {
+            _ if c == SAND_START => Pixel::Highlight,
+            Some(Material::Rock) => Pixel::Shade(0),
+            Some(Material::Sand) => Pixel::Shade(1),
+            None => Pixel::Background,
+        })
+    }
+
+    /// Where the sand at `sand` falls to next, if it can still move
+    fn next_move(&self, sand: Coord) -> Option<Coord> {
+        let down = sand.step(Direction::Down);
+        let down_left = down.step(Direction::Left);
+        let down_right = down.step(Direction::Right);
+
+        if self.floor_is_solid && down.y >= self.floor {
+            return None;
+        }
+
+        [down, down_left, down_right]
+            .into_iter()
+            .find(|c| !self.cave.contains(*c))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    fn example_input_part_1() {
+    fn test_rocks_example() {
         let input = include_str!("example.input");
-        let ans = part_1(input);
+        let parsed = parse_all(input, Maze::parse).unwrap();
 
-        assert_eq!(ans, 24);
+        assert_eq!(parsed.initial_rocks().len(), 20);
     }
 
     #[test]
-    fn my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = part_1(input);
-
-        assert_eq!(ans, 961);
+    fn draws_a_frame_for_every_grain() {
+        let maze = RegolithReservoir::parse(include_str!("example.input")).unwrap();
+        let mut animation = Animation::new();
+        RegolithReservoir::draw(&maze, &Params::default(), &mut animation).unwrap();
+
+        // The empty cave, then a frame after each of the 24 grains settles
+        assert_eq!(animation.frames().len(), 25);
+
+        let sand = animation.last().unwrap();
+        assert_eq!((sand.width(), sand.height()), (12, 10));
+        assert_eq!(sand.get(Coord::new(7, 8)), Some(Pixel::Shade(1)));
+        assert_eq!(sand.get(Coord::new(7, 9)), Some(Pixel::Shade(0)));
     }
 
     #[test]
-    fn test_rocks_example() {
-        let input = include_str!("example.input");
-        let parsed = Maze::parse(input);
+    fn parse_error_on_diagonal_line() {
+        let input = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,5\n";
+        let err = RegolithReservoir::parse(input).unwrap_err();
 
-        assert_eq!(parsed.initial_rocks().len(), 20);
+        assert_eq!((err.line, err.column), (2, 10));
+        assert_eq!(err.text, "502,5");
     }
 
     #[test]
-    fn example_input_part_2() {
-        let input = include_str!("example.input");
-        let ans = part_2(input);
+    fn parse_error_on_a_path_with_one_point() {
+        let input = "498,4 -> 498,6\n503,4\n";
+        let err = RegolithReservoir::parse(input).unwrap_err();
 
-        assert_eq!(ans, 93);
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.expected, "-> and another point");
     }
 
-    #[test]
-    fn my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = part_2(input);
-
-        assert_eq!(ans, 26375);
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "( -> |[0-9]|,|\\n| ){0,64}") {
+            let _ = RegolithReservoir::parse(&input);
+        }
     }
 }
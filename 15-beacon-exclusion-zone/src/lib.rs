@@ -1,299 +1,313 @@
-use rayon::prelude::*;
 use std::collections::HashSet;
 
-#[inline(never)]
-pub fn part_1(input: &str, row: i64) -> usize {
-    let parsed = Zone::parse(input);
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_geometry::{Interval, IntervalSet, ManhattanBall, Point};
+use aoc_parse::{lines, parse_all, signed, template, Parsed};
+use aoc_vis::{Animation, Image, Palette, Pixel, Visualise};
+use tracing::debug;
+
+pub struct BeaconExclusionZone;
+
+impl Solution for BeaconExclusionZone {
+    const DAY: u8 = 15;
+    const NAME: &'static str = "Beacon Exclusion Zone";
+    const PARAMS: &'static [&'static str] = &["row", "max"];
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
+
+    type Input = Zone;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, Zone::parse).map_err(|e| e.locate(Self::DAY, input))
+    }
 
-    let beacons = parsed.beacons();
+    fn part_1(zone: &Self::Input, params: &Params) -> Result<usize, SolveError> {
+        Ok(count_no_beacon_positions(
+            zone,
+            params.get_or("row", 2_000_000),
+        ))
+    }
 
-    // parsed
-    //     .safe_locations()
-    //     .iter()
-    //     .filter(|c| !beacons.contains(c) && c.y == row)
-    //     .count()
+    fn part_2(zone: &Self::Input, params: &Params) -> Result<i64, SolveError> {
+        tuning_frequency(zone, search_max(params)?)
+    }
+}
+
+pub fn part_1(input: &str, row: i64) -> Result<usize, Error> {
+    let parsed = BeaconExclusionZone::parse(input)?;
 
-    let min_x = parsed.min_x();
-    let max_x = parsed.max_x();
+    Ok(BeaconExclusionZone::part_1(
+        &parsed,
+        &Params::new().with("row", row),
+    )?)
+}
+
+pub fn part_2(input: &str, max: i64) -> Result<i64, Error> {
+    let parsed = BeaconExclusionZone::parse(input)?;
+
+    Ok(BeaconExclusionZone::part_2(
+        &parsed,
+        &Params::new().with("max", max),
+    )?)
+}
+
+#[inline(never)]
+fn count_no_beacon_positions(zone: &Zone, row: i64) -> usize {
+    let covered = zone.covered_row(row);
+    let beacons_in_row = zone
+        .beacons()
+        .into_iter()
+        .filter(|beacon| beacon.y == row && covered.contains(beacon.x))
+        .count();
+
+    covered.measure() as usize - beacons_in_row
+}
 
-    (min_x..max_x)
-        .filter(|&x| {
-            let c = Coord { x, y: row };
+const FREQUENCY_PARAM: i64 = 4_000_000;
 
-            parsed.cannot_contain_beacon(c) && !beacons.contains(&c)
-        })
-        .count()
+/// How far the search area for the distress beacon goes along both axes.
+/// Its tuning frequency has to fit in an `i64`, which caps how far that can be
+fn search_max(params: &Params) -> Result<i64, SolveError> {
+    params.get_within("max", 4_000_000, 0..=i64::MAX / (FREQUENCY_PARAM + 1))
 }
 
 #[inline(never)]
-pub fn part_2<const MAX: i64>(input: &str) -> i64 {
-    const FREQUENCY_PARAM: i64 = 4_000_000;
+fn tuning_frequency(zone: &Zone, max: i64) -> Result<i64, SolveError> {
+    let c = distress_beacon(zone, max).ok_or_else(|| {
+        SolveError::NoAnswer(format!(
+            "the sensors can see everywhere from 0 to {max} along both axes"
+        ))
+    })?;
+    debug!(x = c.x, y = c.y, "Found the distress beacon");
+
+    Ok(c.x * FREQUENCY_PARAM + c.y)
+}
 
-    let parsed = Zone::parse(input);
+/// The only spot from 0 to `max` along both axes that no sensor can see
+fn distress_beacon(zone: &Zone, max: i64) -> Option<Point> {
+    let search_area = IntervalSet::from(Interval::new(0, max)?);
 
-    let mut y = 0;
+    (0..=max).find_map(|y| {
+        let unseen = search_area.difference(&zone.covered_row(y));
+        let x = unseen.iter().next()?.start();
 
-    while y < MAX {
-        let mut x = 0;
+        Some(Point::new(x, y))
+    })
+}
 
-        while x < MAX {
-            let c = Coord { x, y };
+/// How many pixels across the picture of the search area is, at most
+const PICTURE_SIZE: i64 = 400;
+
+impl Visualise for BeaconExclusionZone {
+    const PALETTE: Palette = Palette::HEAT;
+
+    /// Shades the search area from part 2 by how many sensors can see each
+    /// spot, adding the sensors one at a time, then picks out the distress
+    /// beacon. Real inputs are far too big to draw a pixel per spot, so each
+    /// pixel stands for the spot at its top left
+    fn draw(
+        zone: &Self::Input,
+        params: &Params,
+        animation: &mut Animation,
+    ) -> Result<(), SolveError> {
+        let max = search_max(params)?;
+        let size = (max + 1).min(PICTURE_SIZE);
+        let spot = |pixel: isize| pixel as i64 * (max + 1) / size;
+
+        // Shade n is seen by n + 1 sensors, with anything seen by more
+        // sharing the last shade
+        const SHADES: u8 = 4;
+        let picture = |sensors: &[Sensor]| {
+            let mut image = Image::new(size as usize, size as usize, SHADES);
+
+            for y in 0..size as isize {
+                for x in 0..size as isize {
+                    let c = Point {
+                        x: spot(x),
+                        y: spot(y),
+                    };
+                    let seen_by = sensors
+                        .iter()
+                        .filter(|s| s.cannot_contain_beacon(c))
+                        .count();
+
+                    if seen_by > 0 {
+                        let shade = (seen_by - 1).min(SHADES as usize - 1) as u8;
+                        image.set(aoc_grid::Coord::new(x, y), Pixel::Shade(shade));
+                    }
+                }
+            }
 
-            let biggest_jump_possible = parsed.biggest_jump_possible(c);
+            image
+        };
+
+        for i in 0..=zone.sensors.len() {
+            animation.step(|| picture(&zone.sensors[..i]));
+        }
 
-            let jump_x = if let Some(x) = biggest_jump_possible {
-                x
-            } else {
-                return c.x * FREQUENCY_PARAM + c.y;
-            };
+        let mut image = picture(&zone.sensors);
+        if let Some(beacon) = distress_beacon(zone, max) {
+            let pixel = |spot: i64| (spot * size / (max + 1)) as isize;
+            let centre = aoc_grid::Coord::new(pixel(beacon.x), pixel(beacon.y));
 
-            x += jump_x
+            for c in centre.neighbours_8().chain([centre]) {
+                image.set(c, Pixel::Highlight);
+            }
         }
 
-        y += 1;
-    }
+        animation.finish(image);
 
-    panic!("We looked at every coordinate and didn't find the whole");
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
-struct Zone {
+pub struct Zone {
     sensors: Vec<Sensor>,
 }
+
 impl Zone {
-    fn parse(input: &str) -> Self {
-        let sensors = input.lines().map(|l| Sensor::parse(l)).collect();
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (sensors, rest) = lines(Sensor::parse)(input)?;
 
-        Self { sensors }
-    }
+        if sensors.is_empty() {
+            return Err(Unexpected::new(input, "at least one sensor"));
+        }
 
-    #[inline(never)]
-    fn safe_locations(&self) -> HashSet<Coord> {
-        self.sensors
-            .iter()
-            .flat_map(|s| s.safe_locations())
-            .collect()
+        Ok((Self { sensors }, rest))
     }
 
     #[inline(never)]
-    fn beacons(&self) -> HashSet<Coord> {
+    fn beacons(&self) -> HashSet<Point> {
         self.sensors.iter().map(|s| s.closest_beacon_pos).collect()
     }
 
+    /// Every `x` in row `y` that at least one sensor can see
     #[inline(never)]
-    fn min_x(&self) -> i64 {
-        self.sensors.iter().map(|s| s.min_x()).min().unwrap()
-    }
-
-    #[inline(never)]
-    fn max_x(&self) -> i64 {
-        self.sensors.iter().map(|s| s.max_x()).max().unwrap()
-    }
-
-    #[inline(never)]
-    fn cannot_contain_beacon(&self, c: Coord) -> bool {
-        self.sensors.iter().any(|s| s.cannot_contain_beacon(c))
-    }
-
-    fn biggest_jump_possible(&self, c: Coord) -> Option<i64> {
-        self.sensors
-            .iter()
-            .map(|s| s.jump_for(c))
-            .max()
-            .expect("There is at least one sensor")
+    fn covered_row(&self, y: i64) -> IntervalSet {
+        self.sensors.iter().filter_map(|s| s.range.row(y)).collect()
     }
 }
 
 #[derive(Debug)]
 struct Sensor {
-    pos: Coord,
-    closest_beacon_pos: Coord,
-    disance_to_closest_beacon: u64,
+    /// Everywhere no closer to the sensor than its closest beacon, which is
+    /// everywhere another beacon can't be
+    range: ManhattanBall,
+    closest_beacon_pos: Point,
 }
 
 impl Sensor {
-    fn parse(input: &str) -> Sensor {
-        let r = regex::Regex::new(
-            r"Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)",
-        )
-        .unwrap();
-        let captures = r.captures(input).expect("Each line should match the regex");
-
-        let self_x = captures.get(1).unwrap().as_str().parse().unwrap();
-        let self_y = captures.get(2).unwrap().as_str().parse().unwrap();
-
-        let self_pos = Coord {
-            x: self_x,
-            y: self_y,
-        };
-
-        let beacon_x = captures.get(3).unwrap().as_str().parse().unwrap();
-        let beacon_y = captures.get(4).unwrap().as_str().parse().unwrap();
-
-        let beacon_pos = Coord {
-            x: beacon_x,
-            y: beacon_y,
-        };
-
-        let distance_to_closest_beacon = self_pos.dist(beacon_pos);
-
-        Self {
-            pos: self_pos,
-            closest_beacon_pos: beacon_pos,
-            disance_to_closest_beacon: distance_to_closest_beacon,
-        }
+    fn parse(input: &str) -> Parsed<'_, Sensor> {
+        let ([self_x, self_y, beacon_x, beacon_y], rest) = template(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            signed,
+        )(input)?;
+
+        let self_pos = Point::new(self_x, self_y);
+        let beacon_pos = Point::new(beacon_x, beacon_y);
+
+        Ok((
+            Self {
+                range: ManhattanBall::reaching(self_pos, beacon_pos),
+                closest_beacon_pos: beacon_pos,
+            },
+            rest,
+        ))
     }
 
     #[inline(never)]
-    fn distance_to_closest_beacon(&self) -> u64 {
-        self.disance_to_closest_beacon
-    }
-
-    #[inline(never)]
-    fn safe_locations(&self) -> Vec<Coord> {
-        let max_distance = self.distance_to_closest_beacon();
-        let max_distance_i = max_distance as i64;
-
-        let mut safe_locations = vec![];
-
-        for x_diff in -max_distance_i..=max_distance_i {
-            for y_diff in -max_distance_i..=max_distance_i {
-                let x = self.pos.x + x_diff;
-                let y = self.pos.y + y_diff;
-
-                let c = Coord { x, y };
-
-                if self.pos.dist(c) <= max_distance {
-                    safe_locations.push(c);
-                }
-            }
-        }
-
-        safe_locations
-    }
-
-    #[inline(never)]
-    fn min_x(&self) -> i64 {
-        self.pos.x - self.distance_to_closest_beacon() as i64
-    }
-
-    #[inline(never)]
-    fn max_x(&self) -> i64 {
-        self.pos.x + self.distance_to_closest_beacon() as i64
-    }
-
-    #[inline(never)]
-    fn cannot_contain_beacon(&self, c: Coord) -> bool {
-        self.pos.dist(c) <= self.distance_to_closest_beacon()
-    }
-
-    fn mark_no_beacon<const N: usize>(
-        &self,
-        cannot_contain_beacon: &mut [bool; N],
-        max_value: i64,
-    ) {
-        let max_distance = self.distance_to_closest_beacon();
-        let max_distance_i = max_distance as i64;
-
-        for x_diff in -max_distance_i..=max_distance_i {
-            let max_y = max_distance_i - x_diff.abs();
-
-            for y_diff in -max_y..=max_y {
-                let x = self.pos.x + x_diff;
-                let y = self.pos.y + y_diff;
-
-                if x >= 0 && x < max_value && y >= 0 && y < max_value {
-                    cannot_contain_beacon[((x * max_value) + y) as usize] = true;
-                }
-            }
-        }
-    }
-
-    fn jump_for(&self, c: Coord) -> Option<i64> {
-        let dist_to_beacon = self.disance_to_closest_beacon as i64;
-
-        let x_diff = self.pos.x.abs_diff(c.x) as i64;
-        let y_diff = self.pos.y.abs_diff(c.y) as i64;
-        let dist = x_diff + y_diff;
-
-        if dist > dist_to_beacon {
-            None
-        } else {
-            let width_given_y_position = dist_to_beacon - y_diff;
-
-            let ans = if (self.pos.x - c.x) < 0 {
-                Some(width_given_y_position - x_diff + 1)
-            } else {
-                Some(width_given_y_position + x_diff + 1)
-            };
-
-            ans
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Coord {
-    x: i64,
-    y: i64,
-}
-
-impl Coord {
-    #[inline(never)]
-    fn dist(&self, closest_beacon_pos: Coord) -> u64 {
-        self.x.abs_diff(closest_beacon_pos.x) + self.y.abs_diff(closest_beacon_pos.y)
+    fn cannot_contain_beacon(&self, c: Point) -> bool {
+        self.range.contains(c)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    fn example_input_part_1() {
-        let input = include_str!("example.input");
-        let ans = part_1(input, 10);
-
-        assert_eq!(ans, 26);
+    fn prove_option_ordering_works_as_i_think_it_does() {
+        assert!(None < Some(1));
+        assert!(Some(1) < Some(2));
     }
 
     #[test]
-    fn my_input_part_1() {
-        let input = include_str!("my.input");
-        let ans = part_1(input, 2000000);
+    fn covers_rows_with_intervals() {
+        let input = include_str!("example.input");
+        let zone = parse_all(input, Zone::parse).unwrap();
+
+        let covered: Vec<_> = zone.covered_row(11).iter().collect();
 
-        assert_eq!(ans, 4582667);
+        assert_eq!(
+            covered,
+            [
+                Interval::new(-3, 13).unwrap(),
+                Interval::new(15, 25).unwrap()
+            ]
+        );
     }
 
     #[test]
-    fn example_input_part_2() {
-        let input = include_str!("example.input");
-        let ans = part_2::<20>(input);
-
-        assert_eq!(ans, 56000011);
+    fn draws_the_distress_beacon() {
+        let zone = BeaconExclusionZone::parse(include_str!("example.input")).unwrap();
+        let params = Params::new().with("max", 20);
+        let mut animation = Animation::new();
+        BeaconExclusionZone::draw(&zone, &params, &mut animation).unwrap();
+
+        assert_eq!(animation.frames().len(), zone.sensors.len() + 2);
+
+        let area = animation.last().unwrap();
+        assert_eq!((area.width(), area.height()), (21, 21));
+        assert_eq!(
+            area.get(aoc_grid::Coord::new(14, 11)),
+            Some(Pixel::Highlight)
+        );
+        assert_eq!(
+            animation.frames()[0].get(aoc_grid::Coord::ORIGIN),
+            Some(Pixel::Background)
+        );
     }
 
     #[test]
-    fn my_input_part_2() {
-        let input = include_str!("my.input");
-        let ans = part_2::<4_000_000>(input);
+    fn rejects_search_areas_that_cant_be_searched() {
+        let zone = BeaconExclusionZone::parse(include_str!("example.input")).unwrap();
 
-        assert_eq!(ans, 10961118625406);
-    }
+        for max in [-1, -20, i64::MAX] {
+            let params = Params::new().with("max", max);
 
-    #[test]
-    fn prove_option_ordering_works_as_i_think_it_does() {
-        assert!(None < Some(1));
-        assert!(Some(1) < Some(2));
+            assert!(matches!(
+                BeaconExclusionZone::part_2(&zone, &params),
+                Err(SolveError::BadParam { .. })
+            ));
+            assert!(BeaconExclusionZone::draw(&zone, &params, &mut Animation::new()).is_err());
+        }
+
+        // The sensors see all of the first few rows and columns
+        assert!(matches!(
+            BeaconExclusionZone::part_2(&zone, &Params::new().with("max", 0)),
+            Err(SolveError::NoAnswer(_))
+        ));
     }
 
     #[test]
-    fn test_jumping() {
-        let input = include_str!("example.input");
-        let parsed = Zone::parse(input);
+    fn parse_error_on_malformed_sensor() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16: closest beacon at x=10, y=16\n";
+        let err = BeaconExclusionZone::parse(input).unwrap_err();
 
-        let jump = parsed.biggest_jump_possible(Coord { x: 10, y: 11 });
+        assert_eq!((err.line, err.column), (2, 1));
+    }
 
-        assert_eq!(jump, Some(4))
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "(Sensor at x=|, y=|: closest beacon is at x=|-?[0-9]{1,3}|\\n){0,32}") {
+            let _ = BeaconExclusionZone::parse(&input);
+        }
     }
 }
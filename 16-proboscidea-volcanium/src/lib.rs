@@ -0,0 +1,350 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+};
+
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_parse::{lines, one_of, parse_all, separated, spanned, tag, unsigned, Parsed, Spanned};
+use tracing::debug;
+
+/// The valve everyone starts at
+const START: &str = "AA";
+
+/// Sets of opened valves are kept as bits of a `u64`
+const MAX_USEFUL_VALVES: usize = 64;
+
+/// One line of the scan, like
+/// `Valve BB has flow rate=13; tunnels lead to valves CC, AA`
+#[derive(Debug, Clone)]
+struct Scan<'a> {
+    name: Spanned<'a, &'a str>,
+    flow_rate: u32,
+    tunnels: Vec<Spanned<'a, &'a str>>,
+}
+
+const TUNNELS: &[(&str, ())] = &[
+    ("; tunnels lead to valves ", ()),
+    ("; tunnel leads to valve ", ()),
+];
+
+/// A valve's name, which is all capital letters
+fn valve(input: &str) -> Parsed<'_, &str> {
+    let end = input
+        .find(|c: char| !c.is_ascii_uppercase())
+        .unwrap_or(input.len());
+
+    if end == 0 {
+        let first = input.chars().next().map_or(0, char::len_utf8);
+        return Err(Unexpected::new(&input[..first], "a valve name"));
+    }
+
+    Ok((&input[..end], &input[end..]))
+}
+
+impl<'a> Scan<'a> {
+    fn parse(input: &'a str) -> Parsed<'a, Self> {
+        let (_, input) = tag("Valve ")(input)?;
+        let (name, input) = spanned(valve)(input)?;
+        let (_, input) = tag(" has flow rate=")(input)?;
+        let (flow_rate, input) = unsigned(input)?;
+        let (_, input) = one_of(TUNNELS, "the tunnels leading away")(input)?;
+        let (tunnels, input) = separated(spanned(valve), ", ")(input)?;
+
+        Ok((
+            Self {
+                name,
+                flow_rate,
+                tunnels,
+            },
+            input,
+        ))
+    }
+}
+
+/// The valves worth opening, and how long it takes to walk between them.
+/// Valves with no flow are only ever walked through, so they're left out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Volcano {
+    flow_rates: Vec<u32>,
+    /// Minutes from each valve worth opening to every other, with the valve
+    /// we start at added on the end. `None` when there's no way through
+    distances: Vec<Vec<Option<u32>>>,
+}
+
+impl Volcano {
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (scans, rest) = lines(Scan::parse)(input)?;
+
+        Ok((Self::compress(&scans, input)?, rest))
+    }
+
+    /// Works out the distances between the valves worth opening, checking
+    /// the tunnels all lead somewhere on the way
+    fn compress<'a>(scans: &[Scan<'a>], input: &'a str) -> Result<Self, Unexpected<'a>> {
+        let mut indices = HashMap::new();
+        for (i, scan) in scans.iter().enumerate() {
+            if indices.insert(scan.name.value, i).is_some() {
+                return Err(Unexpected::new(
+                    scan.name.span,
+                    "a valve not already scanned",
+                ));
+            }
+        }
+
+        let tunnels = scans
+            .iter()
+            .map(|scan| {
+                scan.tunnels
+                    .iter()
+                    .map(|tunnel| {
+                        indices
+                            .get(tunnel.value)
+                            .copied()
+                            .ok_or_else(|| Unexpected::new(tunnel.span, "a valve in the scan"))
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<usize>>, _>>()?;
+
+        let useful: Vec<&Scan> = scans.iter().filter(|scan| scan.flow_rate > 0).collect();
+        if let Some(scan) = useful.get(MAX_USEFUL_VALVES) {
+            return Err(Unexpected::new(
+                scan.name.span,
+                format!("at most {MAX_USEFUL_VALVES} valves with any flow"),
+            ));
+        }
+
+        let start = indices
+            .get(START)
+            .copied()
+            .ok_or_else(|| Unexpected::missing_after(input.trim_end(), "valve AA"))?;
+
+        let stops: Vec<usize> = useful
+            .iter()
+            .map(|scan| indices[scan.name.value])
+            .chain([start])
+            .collect();
+
+        let distances = stops
+            .iter()
+            .map(|&from| {
+                let steps = steps_from(from, &tunnels);
+                stops.iter().map(|&to| steps[to]).collect()
+            })
+            .collect();
+
+        Ok(Self {
+            flow_rates: useful.iter().map(|scan| scan.flow_rate).collect(),
+            distances,
+        })
+    }
+
+    fn start(&self) -> usize {
+        self.flow_rates.len()
+    }
+
+    /// The most pressure that can be released by opening each set of valves
+    /// within `minutes`, for every set that can be opened in time
+    fn most_pressure_by_opened(&self, minutes: u32) -> HashMap<u64, u32> {
+        let mut search = Search {
+            volcano: self,
+            best: HashMap::new(),
+            seen: HashMap::new(),
+        };
+        search.visit(self.start(), minutes, 0, 0);
+
+        debug!(
+            minutes,
+            sets = search.best.len(),
+            states = search.seen.len(),
+            "Searched the valves"
+        );
+
+        search.best
+    }
+}
+
+/// Minutes to walk from valve `from` to every other, by a breadth first search
+fn steps_from(from: usize, tunnels: &[Vec<usize>]) -> Vec<Option<u32>> {
+    let mut steps = vec![None; tunnels.len()];
+    steps[from] = Some(0);
+
+    let mut queue = VecDeque::from([from]);
+    while let Some(valve) = queue.pop_front() {
+        let next = steps[valve].map(|s| s + 1);
+
+        for &neighbour in &tunnels[valve] {
+            if steps[neighbour].is_none() {
+                steps[neighbour] = next;
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    steps
+}
+
+/// A depth first search over the orders the valves could be opened in
+struct Search<'a> {
+    volcano: &'a Volcano,
+    /// The most pressure released by opening each set of valves
+    best: HashMap<u64, u32>,
+    /// The most pressure released on the way to standing at a valve with
+    /// some minutes left and a set of valves open. Getting there again with
+    /// no more released can't end any better, so isn't worth following
+    seen: HashMap<(usize, u32, u64), u32>,
+}
+
+impl Search<'_> {
+    fn visit(&mut self, at: usize, minutes: u32, opened: u64, released: u32) {
+        match self.seen.get(&(at, minutes, opened)) {
+            Some(&before) if before >= released => return,
+            _ => self.seen.insert((at, minutes, opened), released),
+        };
+
+        let best = self.best.entry(opened).or_default();
+        *best = (*best).max(released);
+
+        for (valve, &flow_rate) in self.volcano.flow_rates.iter().enumerate() {
+            if opened & 1 << valve != 0 {
+                continue;
+            }
+
+            // Walking there, then a minute to open it
+            let Some(cost) = self.volcano.distances[at][valve].map(|d| d + 1) else {
+                continue;
+            };
+            if cost >= minutes {
+                continue;
+            }
+
+            let left = minutes - cost;
+            self.visit(
+                valve,
+                left,
+                opened | 1 << valve,
+                released + flow_rate * left,
+            );
+        }
+    }
+}
+
+pub struct ProboscideaVolcanium;
+
+impl Solution for ProboscideaVolcanium {
+    const DAY: u8 = 16;
+    const NAME: &'static str = "Proboscidea Volcanium";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("synthetic", include_str!("synthetic.input")),
+    ];
+
+    type Input = Volcano;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, Volcano::parse).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(volcano: &Self::Input, _params: &Params) -> Result<u32, SolveError> {
+        Ok(volcano
+            .most_pressure_by_opened(30)
+            .into_values()
+            .max()
+            .unwrap_or(0))
+    }
+
+    /// We and the elephant open valves at the same time, so between us we
+    /// open two sets of valves with nothing in common
+    fn part_2(volcano: &Self::Input, _params: &Params) -> Result<u32, SolveError> {
+        let mut sets: Vec<(u64, u32)> = volcano.most_pressure_by_opened(26).into_iter().collect();
+        sets.sort_unstable_by_key(|&(_, released)| Reverse(released));
+
+        let mut most = 0;
+        for (i, &(ours, released)) in sets.iter().enumerate() {
+            // Sorted from most released down, so nothing later can beat it
+            if released * 2 <= most {
+                break;
+            }
+
+            for &(elephants, elephant_released) in &sets[i..] {
+                if released + elephant_released <= most {
+                    break;
+                }
+                if ours & elephants == 0 {
+                    most = released + elephant_released;
+                }
+            }
+        }
+
+        Ok(most)
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u32, Error> {
+    let volcano = ProboscideaVolcanium::parse(input)?;
+
+    Ok(ProboscideaVolcanium::part_1(&volcano, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<u32, Error> {
+    let volcano = ProboscideaVolcanium::parse(input)?;
+
+    Ok(ProboscideaVolcanium::part_2(&volcano, &Params::default())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn compresses_to_valves_with_flow() {
+        let volcano = ProboscideaVolcanium::parse(include_str!("example.input")).unwrap();
+
+        // BB, CC, DD, EE, HH and JJ, then AA
+        assert_eq!(volcano.flow_rates, [13, 2, 20, 3, 22, 21]);
+        assert_eq!(volcano.distances[volcano.start()][4], Some(5));
+        assert_eq!(volcano.distances[4][5], Some(7));
+        assert_eq!(volcano.distances[2][2], Some(0));
+    }
+
+    #[test]
+    fn only_opens_valves_in_time() {
+        let volcano = ProboscideaVolcanium::parse(include_str!("example.input")).unwrap();
+        let best = volcano.most_pressure_by_opened(3);
+
+        // Three minutes is only enough to open BB or DD, with one minute of
+        // flow left
+        assert_eq!(best, HashMap::from([(0, 0), (0b1, 13), (0b100, 20)]));
+    }
+
+    #[test]
+    fn parse_error_on_unknown_tunnel() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=13; tunnel leads to valve AA\n";
+        let err = ProboscideaVolcanium::parse(input).unwrap_err();
+
+        assert_eq!((err.line, err.column), (1, 54));
+        assert_eq!(err.text, "CC");
+    }
+
+    #[test]
+    fn parse_error_without_a_start() {
+        let input = "Valve BB has flow rate=13; tunnel leads to valve BB\n";
+        let err = ProboscideaVolcanium::parse(input).unwrap_err();
+
+        assert_eq!(err.expected, "valve AA");
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(
+            input in "(Valve [A-C]{2} has flow rate=[0-9]{1,2}; tunnels? leads? to valves? [A-C]{2}(, [A-C]{2}){0,2}\n){0,6}",
+        ) {
+            let _ = ProboscideaVolcanium::parse(&input);
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError};
+use aoc_grid::Coord;
+use aoc_parse::{chars, complete, label, parse_all};
+use aoc_vis::{Animation, Image, Pixel, Visualise};
+use tracing::debug;
+
+/// How wide the chamber is
+const WIDTH: usize = 7;
+
+/// Rows are bitmasks with the leftmost column in the highest of `WIDTH` bits
+const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+const RIGHT_WALL: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// A rock as it falls, one bitmask per row from the bottom up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rock {
+    rows: [u8; 4],
+    height: usize,
+}
+
+/// The rocks in the order they fall, each starting two units in from the
+/// left wall
+const ROCKS: [Rock; 5] = [
+    // ####
+    Rock {
+        rows: [0b0011110, 0, 0, 0],
+        height: 1,
+    },
+    // .#.
+    // ###
+    // .#.
+    Rock {
+        rows: [0b0001000, 0b0011100, 0b0001000, 0],
+        height: 3,
+    },
+    // ..#
+    // ..#
+    // ###
+    Rock {
+        rows: [0b0011100, 0b0000100, 0b0000100, 0],
+        height: 3,
+    },
+    // #
+    // #
+    // #
+    // #
+    Rock {
+        rows: [0b0010000, 0b0010000, 0b0010000, 0b0010000],
+        height: 4,
+    },
+    // ##
+    // ##
+    Rock {
+        rows: [0b0011000, 0b0011000, 0, 0],
+        height: 2,
+    },
+];
+
+impl Rock {
+    fn rows(&self) -> &[u8] {
+        &self.rows[..self.height]
+    }
+
+    /// Where a jet of gas pushes the rock, unless a wall is in the way
+    fn pushed(self, jet: Jet) -> Option<Rock> {
+        let (wall, shift): (u8, fn(u8) -> u8) = match jet {
+            Jet::Left => (LEFT_WALL, |row| row << 1),
+            Jet::Right => (RIGHT_WALL, |row| row >> 1),
+        };
+
+        if self.rows().iter().any(|row| row & wall != 0) {
+            return None;
+        }
+
+        Some(Rock {
+            rows: self.rows.map(shift),
+            ..self
+        })
+    }
+}
+
+/// The chamber after some rocks have come to rest
+#[derive(Debug, Clone)]
+struct Frame<'a> {
+    jets: &'a [Jet],
+    /// The rock in each row, from the floor up. The top row always has
+    /// rock in it
+    rows: Vec<u8>,
+    /// Rocks that have come to rest so far
+    rocks: u64,
+    /// How many rocks fall before we stop
+    limit: u64,
+    /// The next jet to push a rock
+    jet: usize,
+    /// The rock that came to rest last, and the row its bottom is in
+    last: Option<(Rock, usize)>,
+}
+
+/// Everything about the chamber that decides how the next rocks fall, for
+/// spotting when it starts repeating itself: the next rock, the next jet, and
+/// how far down from the top each column's highest rock is
+type State = (usize, usize, [usize; WIDTH]);
+
+impl<'a> Frame<'a> {
+    fn new(jets: &'a [Jet], limit: u64) -> Self {
+        Self {
+            jets,
+            rows: vec![],
+            rocks: 0,
+            limit,
+            jet: 0,
+            last: None,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Drops the next rock until it comes to rest
+    fn next(self) -> Option<Frame<'a>> {
+        if self.rocks == self.limit {
+            return None;
+        }
+
+        let mut rock = ROCKS[(self.rocks % ROCKS.len() as u64) as usize];
+        let mut bottom = self.height() + 3;
+        let mut jet = self.jet;
+
+        loop {
+            let pushed = rock.pushed(self.jets[jet]);
+            jet = (jet + 1) % self.jets.len();
+
+            if let Some(pushed) = pushed.filter(|pushed| !self.hits(pushed, bottom)) {
+                rock = pushed;
+            }
+
+            if bottom == 0 || self.hits(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+
+        let mut rows = self.rows;
+        for (i, row) in rock.rows().iter().enumerate() {
+            match rows.get_mut(bottom + i) {
+                Some(existing) => *existing |= row,
+                None => rows.push(*row),
+            }
+        }
+
+        Some(Frame {
+            rows,
+            rocks: self.rocks + 1,
+            jet,
+            last: Some((rock, bottom)),
+            ..self
+        })
+    }
+
+    /// Drops every rock that's left
+    fn settle(mut self) -> Frame<'a> {
+        while self.rocks < self.limit {
+            self = self.next().expect("There are rocks left to drop");
+        }
+
+        self
+    }
+
+    /// Whether `rock` would overlap rock already at rest with its bottom in
+    /// row `bottom`
+    fn hits(&self, rock: &Rock, bottom: usize) -> bool {
+        rock.rows()
+            .iter()
+            .zip(self.rows.iter().skip(bottom))
+            .any(|(rock, row)| rock & row != 0)
+    }
+
+    fn state(&self) -> State {
+        let mut depths = [self.height(); WIDTH];
+
+        for (column, depth) in depths.iter_mut().enumerate() {
+            let bit = LEFT_WALL >> column;
+
+            if let Some(d) = self.rows.iter().rev().position(|row| row & bit != 0) {
+                *depth = d;
+            }
+        }
+
+        (self.rocks as usize % ROCKS.len(), self.jet, depths)
+    }
+
+    /// Shade 0 is the walls and floor and shade 1 is rock, with the rock that
+    /// just landed picked out. The picture is `height` rows tall, plus the
+    /// floor
+    fn picture(&self, height: usize) -> Image {
+        let mut image = Image::new(WIDTH + 2, height + 1, 2);
+        let at = |column: usize, row: usize| {
+            Coord::new(column as isize + 1, (height - 1 - row) as isize)
+        };
+
+        for y in 0..=height {
+            image.set(Coord::new(0, y as isize), Pixel::Shade(0));
+            image.set(Coord::new(WIDTH as isize + 1, y as isize), Pixel::Shade(0));
+        }
+        for x in 0..WIDTH + 2 {
+            image.set(Coord::new(x as isize, height as isize), Pixel::Shade(0));
+        }
+
+        for (y, row) in self.rows.iter().enumerate() {
+            for x in (0..WIDTH).filter(|x| row & LEFT_WALL >> x != 0) {
+                image.set(at(x, y), Pixel::Shade(1));
+            }
+        }
+
+        if let Some((rock, bottom)) = self.last {
+            for (y, row) in rock.rows().iter().enumerate() {
+                for x in (0..WIDTH).filter(|x| row & LEFT_WALL >> x != 0) {
+                    image.set(at(x, bottom + y), Pixel::Highlight);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+/// How tall the tower is after `rocks` rocks have fallen
+///
+/// Once the chamber gets back to a state it's been in before, every rock
+/// from then on repeats what happened since, so the repeats are skipped over
+/// and only what's left after them is dropped
+fn tower_height(jets: &[Jet], rocks: u64) -> u64 {
+    let mut frame = Frame::new(jets, rocks);
+    let mut seen: HashMap<State, (u64, usize)> = HashMap::new();
+    let mut skipped = None;
+
+    while frame.rocks < frame.limit {
+        frame = frame.next().expect("There are rocks left to drop");
+
+        if skipped.is_some() {
+            continue;
+        }
+
+        if let Some((rocks_before, height_before)) =
+            seen.insert(frame.state(), (frame.rocks, frame.height()))
+        {
+            let period = frame.rocks - rocks_before;
+            let growth = (frame.height() - height_before) as u64;
+            let repeats = (frame.limit - frame.rocks) / period;
+
+            debug!(period, growth, repeats, "Found a cycle");
+
+            skipped = Some(repeats * growth);
+            frame.limit -= repeats * period;
+        }
+    }
+
+    frame.height() as u64 + skipped.unwrap_or(0)
+}
+
+pub struct PyroclasticFlow;
+
+impl Solution for PyroclasticFlow {
+    const DAY: u8 = 17;
+    const NAME: &'static str = "Pyroclastic Flow";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("synthetic", include_str!("synthetic.input")),
+    ];
+
+    type Input = Vec<Jet>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    /// The jet pattern is a single line of `<` and `>`
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let pattern = input.strip_suffix('\n').unwrap_or(input);
+        let jets = complete(chars("a jet", |c| c == '<' || c == '>'));
+
+        parse_all(pattern, label(jets, "a line of jets, < or >"))
+            .map(|jets| {
+                jets.chars()
+                    .map(|c| if c == '<' { Jet::Left } else { Jet::Right })
+                    .collect()
+            })
+            .map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(jets: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        Ok(tower_height(jets, 2022))
+    }
+
+    fn part_2(jets: &Self::Input, _params: &Params) -> Result<u64, SolveError> {
+        Ok(tower_height(jets, 1_000_000_000_000))
+    }
+}
+
+impl Visualise for PyroclasticFlow {
+    /// Builds part 1's tower a rock at a time
+    fn draw(
+        jets: &Self::Input,
+        _params: &Params,
+        animation: &mut Animation,
+    ) -> Result<(), SolveError> {
+        let mut frame = Frame::new(jets, 2022);
+
+        // Every picture has to be the same size, so we need to know how tall
+        // the tower ends up first
+        let height = frame.clone().settle().height();
+
+        // Working out the next frame uses up this one, so it's drawn first
+        loop {
+            let picture = frame.picture(height);
+
+            match frame.next() {
+                Some(next) => {
+                    animation.step(|| picture);
+                    frame = next;
+                }
+                None => {
+                    animation.finish(picture);
+
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u64, Error> {
+    let jets = PyroclasticFlow::parse(input)?;
+
+    Ok(PyroclasticFlow::part_1(&jets, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<u64, Error> {
+    let jets = PyroclasticFlow::parse(input)?;
+
+    Ok(PyroclasticFlow::part_2(&jets, &Params::default())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn example() -> Vec<Jet> {
+        PyroclasticFlow::parse(include_str!("example.input")).unwrap()
+    }
+
+    #[test]
+    fn stacks_the_first_rocks() {
+        let jets = example();
+        let frame = Frame::new(&jets, 3).settle();
+
+        // ..#....
+        // ..#....
+        // ####...
+        // ..###..
+        // ...#...
+        // ..####.
+        assert_eq!(
+            frame.rows,
+            [0b0011110, 0b0001000, 0b0011100, 0b1111000, 0b0010000, 0b0010000]
+        );
+    }
+
+    #[test]
+    fn walls_stop_pushes() {
+        let flat = ROCKS[0];
+
+        assert_eq!(flat.pushed(Jet::Right).unwrap().rows[0], 0b0001111);
+        assert_eq!(flat.pushed(Jet::Right).unwrap().pushed(Jet::Right), None);
+        let left = flat.pushed(Jet::Left).unwrap().pushed(Jet::Left).unwrap();
+        assert_eq!(left.rows[0], 0b1111000);
+        assert_eq!(left.pushed(Jet::Left), None);
+    }
+
+    #[test]
+    fn skipping_cycles_matches_dropping_every_rock() {
+        let jets = example();
+
+        for rocks in [100, 1_000, 5_000] {
+            let frame = Frame::new(&jets, rocks).settle();
+
+            assert_eq!(tower_height(&jets, rocks), frame.height() as u64);
+        }
+    }
+
+    #[test]
+    fn draws_a_frame_for_every_rock() {
+        let mut animation = Animation::new();
+        PyroclasticFlow::draw(&example(), &Params::default(), &mut animation).unwrap();
+
+        // The empty chamber, then a frame after each rock comes to rest
+        assert_eq!(animation.frames().len(), 2023);
+
+        let tower = animation.last().unwrap();
+        assert_eq!((tower.width(), tower.height()), (9, 3069));
+        assert_eq!(tower.get(Coord::new(0, 0)), Some(Pixel::Shade(0)));
+        assert_eq!(tower.get(Coord::new(4, 3067)), Some(Pixel::Shade(1)));
+    }
+
+    #[test]
+    fn parse_error_on_unknown_jet() {
+        let err = PyroclasticFlow::parse(">><^<\n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(err.text, "^");
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "[<>\\n ^]{0,64}") {
+            let _ = PyroclasticFlow::parse(&input);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_grid::{Bounds3, Coord3};
+use aoc_parse::{lines, parse_all, signed, spanned, template, Parsed};
+
+/// The furthest apart two cubes can be along any axis. Real droplets are
+/// about 20 across, and part 2 fills the whole box around the droplet with
+/// steam, so one stray cube far away would leave it filling forever
+const MAX_SIZE: usize = 100;
+
+/// Coordinates fit in an `i16`, so stepping just outside the droplet never
+/// overflows
+fn parse_cube(input: &str) -> Parsed<'_, Coord3> {
+    let ([x, y, z], rest) = template("{},{},{}", signed::<i16>)(input)?;
+
+    Ok((Coord3::new(x.into(), y.into(), z.into()), rest))
+}
+
+/// The cubes of lava making up the droplet
+#[derive(Debug, Clone)]
+pub struct Droplet {
+    cubes: HashSet<Coord3>,
+    bounds: Bounds3,
+}
+
+impl Droplet {
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (cubes, rest) = lines(spanned(parse_cube))(input)?;
+
+        let Some(first) = cubes.first() else {
+            return Err(Unexpected::new(input, "at least one cube"));
+        };
+        let mut bounds = Bounds3::around(first.value);
+        for cube in &cubes {
+            bounds.extend(cube.value);
+
+            let (min, max) = (bounds.min, bounds.max);
+            if [
+                min.x.abs_diff(max.x),
+                min.y.abs_diff(max.y),
+                min.z.abs_diff(max.z),
+            ]
+            .iter()
+            .any(|&size| size >= MAX_SIZE)
+            {
+                return Err(Unexpected::new(
+                    cube.span,
+                    "a cube less than 100 away from all the others",
+                ));
+            }
+        }
+
+        let cubes = cubes.into_iter().map(|cube| cube.value).collect();
+        Ok((Self { cubes, bounds }, rest))
+    }
+
+    /// Every face of a cube that doesn't touch another cube, including the
+    /// ones facing into air trapped inside the droplet
+    fn surface_area(&self) -> usize {
+        self.cubes
+            .iter()
+            .flat_map(|cube| cube.neighbours_6())
+            .filter(|neighbour| !self.cubes.contains(neighbour))
+            .count()
+    }
+
+    /// Only the faces steam flowing around the outside could reach. The
+    /// steam fills a box one bigger than the droplet on every side, so it
+    /// can get all the way round, and every face it bumps into on the way
+    /// counts
+    fn exterior_surface_area(&self) -> usize {
+        let mut bounds = self.bounds;
+        bounds.extend(self.bounds.min - Coord3::new(1, 1, 1));
+        bounds.extend(self.bounds.max + Coord3::new(1, 1, 1));
+
+        let mut steam = HashSet::from([bounds.min]);
+        let mut queue = vec![bounds.min];
+        let mut faces = 0;
+
+        while let Some(air) = queue.pop() {
+            for neighbour in air.neighbours_6().filter(|n| bounds.contains(*n)) {
+                if self.cubes.contains(&neighbour) {
+                    faces += 1;
+                } else if steam.insert(neighbour) {
+                    queue.push(neighbour);
+                }
+            }
+        }
+
+        faces
+    }
+}
+
+pub struct BoilingBoulders;
+
+impl Solution for BoilingBoulders {
+    const DAY: u8 = 18;
+    const NAME: &'static str = "Boiling Boulders";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("synthetic", include_str!("synthetic.input")),
+    ];
+
+    type Input = Droplet;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, Droplet::parse).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(droplet: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        Ok(droplet.surface_area())
+    }
+
+    fn part_2(droplet: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        Ok(droplet.exterior_surface_area())
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, Error> {
+    let droplet = BoilingBoulders::parse(input)?;
+
+    Ok(BoilingBoulders::part_1(&droplet, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<usize, Error> {
+    let droplet = BoilingBoulders::parse(input)?;
+
+    Ok(BoilingBoulders::part_2(&droplet, &Params::default())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn two_cubes_share_a_face() {
+        let droplet = BoilingBoulders::parse("1,1,1\n2,1,1\n").unwrap();
+
+        assert_eq!(droplet.surface_area(), 10);
+        assert_eq!(droplet.exterior_surface_area(), 10);
+    }
+
+    #[test]
+    fn trapped_air_only_counts_from_inside() {
+        let droplet = BoilingBoulders::parse(include_str!("example.input")).unwrap();
+
+        // The one cube of air at 2,2,5 is walled in on all six sides
+        assert_eq!(droplet.surface_area() - droplet.exterior_surface_area(), 6);
+    }
+
+    #[test]
+    fn parse_error_on_missing_axis() {
+        let err = BoilingBoulders::parse("2,2,2\n1,2\n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.text, "1,2");
+    }
+
+    #[test]
+    fn parse_error_on_a_cube_too_far_away() {
+        let err = BoilingBoulders::parse("0,0,0\n1,2,3\n1000,1000,1000\n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.text, "1000,1000,1000");
+
+        let err = BoilingBoulders::parse("0,0,0\n0,100,0\n").unwrap_err();
+        assert_eq!(err.text, "0,100,0");
+
+        let err = BoilingBoulders::parse("0,0,0\n1000000,1000000,1000000\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "1000000"));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "([0-9]|,|-|\\n){0,64}") {
+            let _ = BoilingBoulders::parse(&input);
+        }
+
+        #[test]
+        fn the_outside_is_part_of_the_surface(
+            cubes in prop::collection::hash_set((0..5isize, 0..5isize, 0..5isize), 1..40),
+        ) {
+            let input: String = cubes.iter().map(|(x, y, z)| format!("{x},{y},{z}\n")).collect();
+            let droplet = BoilingBoulders::parse(&input).unwrap();
+
+            prop_assert!(droplet.exterior_surface_area() <= droplet.surface_area());
+            prop_assert_eq!(droplet.exterior_surface_area() % 2, 0);
+        }
+    }
+}
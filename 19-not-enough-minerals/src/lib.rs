@@ -0,0 +1,417 @@
+use std::fmt;
+
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError};
+use aoc_parse::{lines, parse_all, template, unsigned, Parsed};
+use rayon::prelude::*;
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mineral {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+impl Mineral {
+    /// Most valuable first, which is the order it's best to try building
+    /// robots in
+    const BY_VALUE: [Mineral; 4] = [
+        Mineral::Geode,
+        Mineral::Obsidian,
+        Mineral::Clay,
+        Mineral::Ore,
+    ];
+}
+
+impl fmt::Display for Mineral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Mineral::Ore => "ore",
+            Mineral::Clay => "clay",
+            Mineral::Obsidian => "obsidian",
+            Mineral::Geode => "geode",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// An amount of each mineral, indexed by [`Mineral`]
+type Minerals = [u32; 4];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blueprint {
+    id: u32,
+    /// What it costs to build a robot that collects each mineral
+    costs: [Minerals; 4],
+    /// The most of each mineral any robot costs. We can only spend that much
+    /// in a minute, so there's no point collecting any faster. Geodes are
+    /// never spent, and always worth more of
+    most_useful: Minerals,
+}
+
+impl Blueprint {
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let ([id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian], rest) =
+            template(
+                "Blueprint {}: Each ore robot costs {} ore. \
+                 Each clay robot costs {} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.",
+                unsigned,
+            )(input)?;
+
+        let costs = [
+            [ore, 0, 0, 0],
+            [clay, 0, 0, 0],
+            [obsidian_ore, obsidian_clay, 0, 0],
+            [geode_ore, 0, geode_obsidian, 0],
+        ];
+        let most_useful = [
+            ore.max(clay).max(obsidian_ore).max(geode_ore),
+            obsidian_clay,
+            geode_obsidian,
+            u32::MAX,
+        ];
+
+        Ok((
+            Self {
+                id,
+                costs,
+                most_useful,
+            },
+            rest,
+        ))
+    }
+
+    /// The build order that cracks the most geodes in `minutes`
+    fn best_plan(&self, minutes: u32) -> Plan {
+        let mut search = Search {
+            blueprint: self,
+            minutes,
+            builds: vec![],
+            best: Plan::default(),
+        };
+
+        search.visit(State {
+            minute: 0,
+            robots: [1, 0, 0, 0],
+            minerals: [0; 4],
+        });
+
+        debug!(
+            blueprint = self.id,
+            geodes = search.best.geodes,
+            plan = %search.best,
+            "Planned the robots"
+        );
+
+        search.best
+    }
+}
+
+/// A robot to start building at the start of a minute, counting from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Build {
+    pub minute: u32,
+    pub robot: Mineral,
+}
+
+/// Which robots to build and when, and how many geodes that cracks
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Plan {
+    pub geodes: u32,
+    pub builds: Vec<Build>,
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let builds: Vec<String> = self
+            .builds
+            .iter()
+            .map(|build| format!("{} at {}", build.robot, build.minute))
+            .collect();
+
+        write!(f, "{}", builds.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    /// Minutes gone so far
+    minute: u32,
+    robots: Minerals,
+    minerals: Minerals,
+}
+
+impl State {
+    /// Waits until the robot for `mineral` can be afforded, then builds it.
+    /// `None` if we'd never have enough, or it wouldn't be ready before
+    /// `minutes` are up
+    fn build(self, costs: &Minerals, mineral: Mineral, minutes: u32) -> Option<State> {
+        let mut waiting = 0;
+
+        for ((&cost, &have), &robots) in costs.iter().zip(&self.minerals).zip(&self.robots) {
+            if cost > have {
+                if robots == 0 {
+                    return None;
+                }
+                waiting = waiting.max((cost - have).div_ceil(robots));
+            }
+        }
+
+        // The robot takes a minute to build, and needs a minute after that
+        // to collect anything
+        let minute = self.minute + waiting + 1;
+        if minute >= minutes {
+            return None;
+        }
+
+        let mut next = State { minute, ..self };
+        for ((mineral, robots), cost) in next.minerals.iter_mut().zip(self.robots).zip(costs) {
+            *mineral = *mineral + robots * (waiting + 1) - cost;
+        }
+        next.robots[mineral as usize] += 1;
+
+        Some(next)
+    }
+
+    /// The geodes we'd have at the end if we stopped building now
+    fn geodes_at(&self, minutes: u32) -> u32 {
+        let geode = Mineral::Geode as usize;
+
+        self.minerals[geode] + self.robots[geode] * (minutes - self.minute)
+    }
+
+    /// More geodes than we could possibly have at the end, as if we built a
+    /// geode robot every minute from now on
+    fn most_geodes_at(&self, minutes: u32) -> u32 {
+        let left = minutes - self.minute;
+
+        self.geodes_at(minutes) + left * left.saturating_sub(1) / 2
+    }
+}
+
+/// A depth first search over which robot to build next, skipping straight
+/// to the minute it can be afforded
+struct Search<'a> {
+    blueprint: &'a Blueprint,
+    minutes: u32,
+    /// The robots built on the way to where we are now
+    builds: Vec<Build>,
+    best: Plan,
+}
+
+impl Search<'_> {
+    fn visit(&mut self, state: State) {
+        let geodes = state.geodes_at(self.minutes);
+        if geodes > self.best.geodes {
+            self.best = Plan {
+                geodes,
+                builds: self.builds.clone(),
+            };
+        }
+
+        if state.most_geodes_at(self.minutes) <= self.best.geodes {
+            return;
+        }
+
+        let left = self.minutes - state.minute;
+
+        for mineral in Mineral::BY_VALUE {
+            // Once what we have and what the robots will still collect covers
+            // spending the most we could every minute left, more robots for
+            // it won't help
+            let i = mineral as usize;
+            let most_useful = self.blueprint.most_useful[i].saturating_mul(left);
+            if state.minerals[i] + state.robots[i] * left >= most_useful {
+                continue;
+            }
+
+            let Some(next) = state.build(&self.blueprint.costs[i], mineral, self.minutes) else {
+                continue;
+            };
+
+            self.builds.push(Build {
+                minute: next.minute,
+                robot: mineral,
+            });
+            self.visit(next);
+            self.builds.pop();
+        }
+    }
+}
+
+pub struct NotEnoughMinerals;
+
+impl Solution for NotEnoughMinerals {
+    const DAY: u8 = 19;
+    const NAME: &'static str = "Not Enough Minerals";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("synthetic", include_str!("synthetic.input")),
+    ];
+
+    type Input = Vec<Blueprint>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, lines(Blueprint::parse)).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(blueprints: &Self::Input, _params: &Params) -> Result<u32, SolveError> {
+        Ok(blueprints
+            .par_iter()
+            .map(|blueprint| blueprint.id * blueprint.best_plan(24).geodes)
+            .sum())
+    }
+
+    /// Only the first three blueprints survived the elephants
+    fn part_2(blueprints: &Self::Input, _params: &Params) -> Result<u32, SolveError> {
+        Ok(blueprints
+            .par_iter()
+            .take(3)
+            .map(|blueprint| blueprint.best_plan(32).geodes)
+            .product())
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u32, Error> {
+    let blueprints = NotEnoughMinerals::parse(input)?;
+
+    Ok(NotEnoughMinerals::part_1(&blueprints, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<u32, Error> {
+    let blueprints = NotEnoughMinerals::parse(input)?;
+
+    Ok(NotEnoughMinerals::part_2(&blueprints, &Params::default())?)
+}
+
+/// The build order each blueprint would follow to crack the most geodes in
+/// `minutes`, in the same order as the blueprints
+pub fn plans(input: &str, minutes: u32) -> Result<Vec<Plan>, ParseError> {
+    let blueprints = NotEnoughMinerals::parse(input)?;
+
+    Ok(blueprints
+        .par_iter()
+        .map(|blueprint| blueprint.best_plan(minutes))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Follows the plan a minute at a time, checking every robot can be
+    /// afforded when it's started, and counts the geodes cracked
+    fn follow(blueprint: &Blueprint, plan: &Plan, minutes: u32) -> u32 {
+        let mut robots: Minerals = [1, 0, 0, 0];
+        let mut minerals: Minerals = [0; 4];
+        let mut builds = plan.builds.iter().peekable();
+
+        for minute in 1..=minutes {
+            let building = builds.next_if(|build| build.minute == minute);
+
+            if let Some(build) = building {
+                for (mineral, cost) in minerals
+                    .iter_mut()
+                    .zip(blueprint.costs[build.robot as usize])
+                {
+                    *mineral = mineral
+                        .checked_sub(cost)
+                        .unwrap_or_else(|| panic!("Can't afford {build:?}"));
+                }
+            }
+            for (mineral, robots) in minerals.iter_mut().zip(robots) {
+                *mineral += robots;
+            }
+            if let Some(build) = building {
+                robots[build.robot as usize] += 1;
+            }
+        }
+
+        assert!(builds.next().is_none(), "Not every robot was built");
+        minerals[Mineral::Geode as usize]
+    }
+
+    #[test]
+    fn plans_crack_as_many_geodes_as_they_say() {
+        let blueprints = NotEnoughMinerals::parse(include_str!("example.input")).unwrap();
+
+        for (blueprint, geodes) in blueprints.iter().zip([9, 12]) {
+            let plan = blueprint.best_plan(24);
+
+            assert_eq!(plan.geodes, geodes);
+            assert_eq!(follow(blueprint, &plan, 24), geodes);
+        }
+    }
+
+    #[test]
+    fn plans_the_example_build_order() {
+        let plans = plans(include_str!("example.input"), 24).unwrap();
+        let builds: Vec<_> = plans[0]
+            .builds
+            .iter()
+            .map(|build| (build.minute, build.robot))
+            .collect();
+
+        // The same order the puzzle walks through for the first blueprint
+        assert_eq!(plans[0].geodes, 9);
+        assert_eq!(
+            builds,
+            [
+                (3, Mineral::Clay),
+                (5, Mineral::Clay),
+                (7, Mineral::Clay),
+                (11, Mineral::Obsidian),
+                (12, Mineral::Clay),
+                (15, Mineral::Obsidian),
+                (18, Mineral::Geode),
+                (21, Mineral::Geode),
+            ]
+        );
+        assert_eq!(plans[1].geodes, 12);
+    }
+
+    #[test]
+    fn parse_error_on_a_missing_cost() {
+        let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+                     Each obsidian robot costs 3 ore and clay. \
+                     Each geode robot costs 2 ore and 7 obsidian.\n";
+        let err = NotEnoughMinerals::parse(input).unwrap_err();
+
+        assert_eq!((err.line, err.column), (1, 1));
+        assert!(err.expected.starts_with("a line like \"Blueprint {}"));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "(Blueprint [0-9]: Each ore robot costs [0-9] ore\\. ?|[0-9]|\\n| ){0,64}") {
+            let _ = NotEnoughMinerals::parse(&input);
+        }
+
+        #[test]
+        fn plans_can_be_followed(
+            ore in 1..5u32,
+            clay in 1..5u32,
+            obsidian in (1..5u32, 1..15u32),
+            geode in (1..5u32, 1..15u32),
+        ) {
+            let input = format!(
+                "Blueprint 1: Each ore robot costs {ore} ore. Each clay robot costs {clay} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.",
+                obsidian.0, obsidian.1, geode.0, geode.1,
+            );
+            let blueprint = parse_all(&input, Blueprint::parse).unwrap();
+            let plan = blueprint.best_plan(20);
+
+            prop_assert_eq!(follow(&blueprint, &plan, 20), plan.geodes);
+        }
+    }
+}
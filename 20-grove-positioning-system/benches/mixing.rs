@@ -0,0 +1,36 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use grove_positioning_system::{mix, mix_naively};
+
+/// Mixes the same numbers once with each approach, at a few sizes, so the
+/// gap between them shows up as the file grows. The numbers are spread
+/// like the real ones, but made up so every size has as many as it needs
+fn bench_mixing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mixing");
+    group.sample_size(10);
+
+    for len in [1_000, 5_000, 20_000] {
+        // A small linear congruential generator keeps the numbers the same
+        // from run to run without pulling in a random number crate
+        let mut seed: u64 = 2022;
+        let numbers: Vec<i64> = (0..len)
+            .map(|_| {
+                seed = seed
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (seed >> 33) as i64 % 20_000 - 10_000
+            })
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("blocks", len), &numbers, |b, numbers| {
+            b.iter(|| mix(black_box(numbers), 1))
+        });
+        group.bench_with_input(BenchmarkId::new("vec", len), &numbers, |b, numbers| {
+            b.iter(|| mix_naively(black_box(numbers), 1))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_mixing);
+criterion_main!(benches);
@@ -0,0 +1,278 @@
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError, Unexpected};
+use aoc_parse::{lines, parse_all, signed, Parsed};
+
+const DECRYPTION_KEY: i64 = 811_589_153;
+
+/// The numbers in the encrypted file, in the order they're mixed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    numbers: Vec<i64>,
+}
+
+impl File {
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (numbers, rest) = lines(signed)(input)?;
+
+        // The grove coordinates are counted from the 0
+        if !numbers.contains(&0) {
+            return Err(Unexpected::missing_after(input.trim_end(), "a 0"));
+        }
+
+        Ok((Self { numbers }, rest))
+    }
+}
+
+/// A circle of numbers, split into blocks of about the square root of how
+/// many there are. Moving a number only shifts the numbers in the blocks it
+/// leaves and joins, and finding where a number is only means looking
+/// through its own block and counting the ones before it
+#[derive(Debug, Clone)]
+struct Circle {
+    /// The numbers, by where they are in the file
+    blocks: Vec<Vec<usize>>,
+    /// Which block each number is in
+    block_of: Vec<usize>,
+    /// Blocks are split back up once they get twice this long
+    block_size: usize,
+}
+
+impl Circle {
+    fn new(len: usize) -> Self {
+        let block_size = (len as f64).sqrt().ceil().max(1.0) as usize;
+        let mut circle = Self {
+            blocks: vec![(0..len).collect()],
+            block_of: vec![0; len],
+            block_size,
+        };
+        circle.rebalance();
+
+        circle
+    }
+
+    fn len(&self) -> usize {
+        self.block_of.len()
+    }
+
+    /// Splits every block back up to `block_size`
+    fn rebalance(&mut self) {
+        let order: Vec<usize> = self.blocks.concat();
+
+        self.blocks = order
+            .chunks(self.block_size)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+
+        for (b, block) in self.blocks.iter().enumerate() {
+            for &i in block {
+                self.block_of[i] = b;
+            }
+        }
+    }
+
+    /// Takes number `i` out of the circle, and says where it was
+    fn remove(&mut self, i: usize) -> usize {
+        let b = self.block_of[i];
+        let within = self.blocks[b]
+            .iter()
+            .position(|&n| n == i)
+            .expect("Numbers are always in the block we think");
+        self.blocks[b].remove(within);
+
+        let before: usize = self.blocks[..b].iter().map(Vec::len).sum();
+        before + within
+    }
+
+    /// Puts number `i` back into the circle so it ends up at `position`
+    fn insert(&mut self, i: usize, mut position: usize) {
+        let last = self.blocks.len() - 1;
+        let b = (0..last)
+            .find(|&b| {
+                let fits = position <= self.blocks[b].len();
+                if !fits {
+                    position -= self.blocks[b].len();
+                }
+                fits
+            })
+            .unwrap_or(last);
+
+        self.blocks[b].insert(position, i);
+        self.block_of[i] = b;
+
+        if self.blocks[b].len() > self.block_size * 2 {
+            self.rebalance();
+        }
+    }
+
+    /// Moves number `i` forwards `by` places, wrapping round the circle
+    fn shift(&mut self, i: usize, by: i64) {
+        // It's taken out before moving, so there's one less place to move
+        // past on the way round
+        let others = self.len() as i64 - 1;
+        if others == 0 {
+            return;
+        }
+
+        let from = self.remove(i) as i64;
+        self.insert(i, (from + by).rem_euclid(others) as usize);
+    }
+
+    fn order(&self) -> impl Iterator<Item = usize> + '_ {
+        self.blocks.iter().flatten().copied()
+    }
+}
+
+/// Mixes the numbers `rounds` times over, giving back the circle they end up
+/// in from wherever it happens to start
+pub fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let mut circle = Circle::new(numbers.len());
+
+    for _ in 0..rounds {
+        for (i, &n) in numbers.iter().enumerate() {
+            circle.shift(i, n);
+        }
+    }
+
+    circle.order().map(|i| numbers[i]).collect()
+}
+
+/// The same as [`mix`], by moving numbers around one big `Vec`. That makes
+/// each move take time in proportion to how many numbers there are, so it's
+/// only kept to check and benchmark [`mix`] against
+pub fn mix_naively(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let mut order: Vec<usize> = (0..numbers.len()).collect();
+    let others = numbers.len() as i64 - 1;
+
+    for _ in 0..rounds {
+        for (i, &n) in numbers.iter().enumerate() {
+            if others == 0 {
+                continue;
+            }
+
+            let from = order.iter().position(|&o| o == i).unwrap();
+            order.remove(from);
+            order.insert((from as i64 + n).rem_euclid(others) as usize, i);
+        }
+    }
+
+    order.into_iter().map(|i| numbers[i]).collect()
+}
+
+/// The numbers 1000, 2000 and 3000 places after the 0, added up
+fn grove_coordinates(mixed: &[i64]) -> i64 {
+    let zero = mixed
+        .iter()
+        .position(|&n| n == 0)
+        .expect("Files always have a 0");
+
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum()
+}
+
+pub struct GrovePositioningSystem;
+
+impl Solution for GrovePositioningSystem {
+    const DAY: u8 = 20;
+    const NAME: &'static str = "Grove Positioning System";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("synthetic", include_str!("synthetic.input")),
+    ];
+
+    type Input = File;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, File::parse).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(file: &Self::Input, _params: &Params) -> Result<i64, SolveError> {
+        Ok(grove_coordinates(&mix(&file.numbers, 1)))
+    }
+
+    fn part_2(file: &Self::Input, _params: &Params) -> Result<i64, SolveError> {
+        let decrypted: Vec<i64> = file.numbers.iter().map(|n| n * DECRYPTION_KEY).collect();
+
+        Ok(grove_coordinates(&mix(&decrypted, 10)))
+    }
+}
+
+pub fn part_1(input: &str) -> Result<i64, Error> {
+    let file = GrovePositioningSystem::parse(input)?;
+
+    Ok(GrovePositioningSystem::part_1(&file, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<i64, Error> {
+    let file = GrovePositioningSystem::parse(input)?;
+
+    Ok(GrovePositioningSystem::part_2(&file, &Params::default())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// The circle read round from the 0, which is the same however it was
+    /// turned
+    fn from_zero(mixed: &[i64]) -> Vec<i64> {
+        let zero = mixed.iter().position(|&n| n == 0).unwrap();
+
+        mixed[zero..]
+            .iter()
+            .chain(&mixed[..zero])
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn mixes_the_example() {
+        let file = GrovePositioningSystem::parse(include_str!("example.input")).unwrap();
+
+        assert_eq!(from_zero(&mix(&file.numbers, 1)), [0, 3, -2, 1, 2, -3, 4]);
+    }
+
+    #[test]
+    fn keeps_duplicates_apart() {
+        let numbers = [0, 1, 1, -1, 1];
+
+        assert_eq!(
+            from_zero(&mix(&numbers, 3)),
+            from_zero(&mix_naively(&numbers, 3))
+        );
+    }
+
+    #[test]
+    fn parse_error_without_a_zero() {
+        let err = GrovePositioningSystem::parse("1\n2\n-3\n").unwrap_err();
+
+        assert_eq!(err.expected, "a 0");
+        assert_eq!(err.line, 3);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "(-?[0-9]{1,3}\\n){0,16}") {
+            let _ = GrovePositioningSystem::parse(&input);
+        }
+
+        #[test]
+        fn mixes_like_moving_numbers_in_a_vec(
+            mut numbers in prop::collection::vec(-50..50i64, 1..60),
+            rounds in 1..3usize,
+            key in prop_oneof![Just(1), Just(DECRYPTION_KEY)],
+        ) {
+            numbers[0] = 0;
+            let numbers: Vec<i64> = numbers.iter().map(|n| n * key).collect();
+
+            prop_assert_eq!(
+                from_zero(&mix(&numbers, rounds)),
+                from_zero(&mix_naively(&numbers, rounds))
+            );
+        }
+    }
+}
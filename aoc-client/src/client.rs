@@ -0,0 +1,264 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{page, Error, History, Submission, Verdict};
+
+/// A logged in connection to the site, for one year's puzzles
+pub struct Client {
+    base_url: String,
+    year: u16,
+    session: String,
+    cache: PathBuf,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub const BASE_URL: &'static str = "https://adventofcode.com";
+
+    /// `session` is the value of the site's `session` cookie. Inputs,
+    /// examples and the submission history are kept under `cache`
+    pub fn new(session: impl Into<String>, cache: impl Into<PathBuf>) -> Self {
+        Self {
+            base_url: Self::BASE_URL.to_string(),
+            year: 2022,
+            session: session.into(),
+            cache: cache.into(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!("aoc-client/", env!("CARGO_PKG_VERSION")))
+                .build(),
+        }
+    }
+
+    /// Talks to somewhere other than the real site, like a [`MockServer`]
+    ///
+    /// [`MockServer`]: crate::mock::MockServer
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// The day's puzzle input, only downloaded if it isn't cached yet
+    pub fn input(&self, day: u8) -> Result<String, Error> {
+        self.cached(day, "input", || self.get(&format!("/day/{day}/input")))
+    }
+
+    /// The first example in the day's puzzle description, only downloaded if
+    /// it isn't cached yet
+    pub fn example(&self, day: u8) -> Result<String, Error> {
+        self.cached(day, "example", || {
+            let html = self.get(&format!("/day/{day}"))?;
+
+            page::example(&html).ok_or(Error::NoExample(day))
+        })
+    }
+
+    /// Submits an answer and records what the site made of it
+    ///
+    /// An answer that was already judged isn't sent again, since the site
+    /// would give the same verdict and make us wait before the next answer.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, Error> {
+        let path = self.history_path();
+        let mut history = History::load(&path)?;
+
+        if let Some(verdict) = history.verdict_for(day, part, answer) {
+            return Ok(verdict.clone());
+        }
+
+        let html = self.post(
+            &format!("/day/{day}/answer"),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+        let verdict = Verdict::from_page(&html);
+
+        history.submissions.push(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+            submitted_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+        });
+        history.save(&path)?;
+
+        Ok(verdict)
+    }
+
+    pub fn history(&self) -> Result<History, Error> {
+        History::load(&self.history_path())
+    }
+
+    fn history_path(&self) -> PathBuf {
+        self.cache.join(self.year.to_string()).join("history.toml")
+    }
+
+    /// Reads `name` from the day's cache, or fetches it and saves it there
+    fn cached(
+        &self,
+        day: u8,
+        name: &str,
+        fetch: impl FnOnce() -> Result<String, Error>,
+    ) -> Result<String, Error> {
+        let dir = self
+            .cache
+            .join(self.year.to_string())
+            .join(format!("day{day:02}"));
+        let path = dir.join(name);
+
+        match fs::read_to_string(&path) {
+            Ok(text) => return Ok(text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(Error::Io(path, e)),
+        }
+
+        let text = fetch()?;
+        write(&dir, &path, &text)?;
+
+        Ok(text)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}{path}", self.base_url, self.year)
+    }
+
+    fn get(&self, path: &str) -> Result<String, Error> {
+        let url = self.url(path);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read(url, response)
+    }
+
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, Error> {
+        let url = self.url(path);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form);
+
+        read(url, response)
+    }
+}
+
+fn read(url: String, response: Result<ureq::Response, ureq::Error>) -> Result<String, Error> {
+    match response {
+        Ok(response) => response.into_string().map_err(|e| Error::Transport {
+            url,
+            message: e.to_string(),
+        }),
+        Err(ureq::Error::Status(status, _)) => Err(Error::Status { url, status }),
+        Err(e) => Err(Error::Transport {
+            url,
+            message: e.to_string(),
+        }),
+    }
+}
+
+fn write(dir: &Path, path: &Path, text: &str) -> Result<(), Error> {
+    fs::create_dir_all(dir).map_err(|e| Error::Io(dir.to_owned(), e))?;
+    fs::write(path, text).map_err(|e| Error::Io(path.to_owned(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{MockDay, MockServer};
+
+    use super::*;
+
+    fn server() -> MockServer {
+        MockServer::start(
+            "secret",
+            vec![MockDay {
+                day: 1,
+                example: "1000\n2000\n\n3000\n".to_string(),
+                input: "100\n\n200\n".to_string(),
+                answers: ["200".to_string(), "300".to_string()],
+            }],
+        )
+    }
+
+    /// A fresh cache for each test, so they can't see each other's downloads
+    fn cache(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-client-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        dir
+    }
+
+    #[test]
+    fn downloads_inputs_and_examples_once() {
+        let server = server();
+        let cache = cache("downloads");
+        let client = Client::new("secret", &cache).with_base_url(server.url());
+
+        assert_eq!(client.input(1).unwrap(), "100\n\n200\n");
+        assert_eq!(client.example(1).unwrap(), "1000\n2000\n\n3000\n");
+        assert_eq!(server.requests(), 2);
+
+        assert_eq!(client.input(1).unwrap(), "100\n\n200\n");
+        assert_eq!(client.example(1).unwrap(), "1000\n2000\n\n3000\n");
+        assert_eq!(server.requests(), 2);
+
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn reports_bad_sessions_and_missing_days() {
+        let server = server();
+        let cache = cache("errors");
+
+        let client = Client::new("wrong", &cache).with_base_url(server.url());
+        assert!(matches!(
+            client.input(1),
+            Err(Error::Status { status: 400, .. })
+        ));
+
+        let client = Client::new("secret", &cache).with_base_url(server.url());
+        assert!(matches!(
+            client.input(2),
+            Err(Error::Status { status: 404, .. })
+        ));
+    }
+
+    #[test]
+    fn submits_answers_and_keeps_a_history() {
+        let server = server();
+        let cache = cache("submits");
+        let client = Client::new("secret", &cache).with_base_url(server.url());
+
+        assert_eq!(client.submit(1, 1, "150").unwrap(), Verdict::TooLow);
+        assert_eq!(client.submit(1, 1, "250").unwrap(), Verdict::TooHigh);
+
+        server.rate_limit_next(65);
+        assert_eq!(
+            client.submit(1, 1, "200").unwrap(),
+            Verdict::RateLimited {
+                wait_secs: Some(65)
+            }
+        );
+        assert_eq!(client.submit(1, 1, "200").unwrap(), Verdict::Correct);
+        assert_eq!(
+            client.submit(1, 2, "three hundred").unwrap(),
+            Verdict::Wrong
+        );
+
+        // Answers that were already judged aren't sent again
+        let requests = server.requests();
+        assert_eq!(client.submit(1, 1, "150").unwrap(), Verdict::TooLow);
+        assert_eq!(server.requests(), requests);
+
+        let history = client.history().unwrap();
+        let verdicts: Vec<_> = history.submissions.iter().map(|s| &s.verdict).collect();
+        assert_eq!(verdicts.len(), 5);
+        assert_eq!(verdicts[3], &Verdict::Correct);
+
+        fs::remove_dir_all(cache).unwrap();
+    }
+}
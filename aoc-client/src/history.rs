@@ -0,0 +1,91 @@
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Verdict};
+
+/// Every answer we've submitted, oldest first
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(rename = "submission", default)]
+    pub submissions: Vec<Submission>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch
+    pub submitted_at: u64,
+}
+
+impl History {
+    /// Loads the history, which is empty before the first submission
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                toml::from_str(&text).map_err(|e| Error::History(path.to_owned(), e.to_string()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::Io(path.to_owned(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let text =
+            toml::to_string(self).map_err(|e| Error::History(path.to_owned(), e.to_string()))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::Io(dir.to_owned(), e))?;
+        }
+
+        fs::write(path, text).map_err(|e| Error::Io(path.to_owned(), e))
+    }
+
+    /// What the site said the last time we submitted this exact answer, if
+    /// it actually judged it
+    pub fn verdict_for(&self, day: u8, part: u8, answer: &str) -> Option<&Verdict> {
+        self.submissions
+            .iter()
+            .rev()
+            .find(|s| s.day == day && s.part == part && s.answer == answer && s.verdict.is_judged())
+            .map(|s| &s.verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml() {
+        let history = History {
+            submissions: vec![
+                Submission {
+                    day: 1,
+                    part: 1,
+                    answer: "24000".to_string(),
+                    verdict: Verdict::RateLimited {
+                        wait_secs: Some(30),
+                    },
+                    submitted_at: 1_670_000_000,
+                },
+                Submission {
+                    day: 1,
+                    part: 1,
+                    answer: "24000".to_string(),
+                    verdict: Verdict::TooLow,
+                    submitted_at: 1_670_000_060,
+                },
+            ],
+        };
+
+        let text = toml::to_string(&history).unwrap();
+        assert_eq!(toml::from_str::<History>(&text).unwrap(), history);
+
+        assert_eq!(history.verdict_for(1, 1, "24000"), Some(&Verdict::TooLow));
+        assert_eq!(history.verdict_for(1, 2, "24000"), None);
+    }
+}
@@ -0,0 +1,58 @@
+//! Talks to the Advent of Code website: downloads puzzle inputs and examples,
+//! keeps them in a local cache so each one is only downloaded once, and
+//! submits answers while keeping a history of everything submitted
+//!
+//! The site needs the `session` cookie of a logged in browser to hand out
+//! inputs and take answers. [`mock::MockServer`] stands in for the site in
+//! tests, so none of this needs the real network to be tested.
+
+use std::{fmt, io, path::PathBuf};
+
+mod client;
+mod history;
+pub mod mock;
+mod page;
+mod verdict;
+
+pub use client::Client;
+pub use history::{History, Submission};
+pub use verdict::Verdict;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The site answered, but not with what we asked for. A bad or expired
+    /// session shows up as a 400, and a day that isn't out yet as a 404
+    Status {
+        url: String,
+        status: u16,
+    },
+    /// We couldn't reach the site at all
+    Transport {
+        url: String,
+        message: String,
+    },
+    /// The puzzle page didn't have an example we could pick out
+    NoExample(u8),
+    Io(PathBuf, io::Error),
+    History(PathBuf, String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Status { url, status: 400 } => {
+                write!(f, "{url} turned us away, is the session token right?")
+            }
+            Error::Status { url, status: 404 } => {
+                write!(f, "{url} doesn't exist, is the puzzle out yet?")
+            }
+            Error::Status { url, status } => write!(f, "{url} answered with status {status}"),
+            Error::Transport { url, message } => write!(f, "Couldn't reach {url}: {message}"),
+            Error::NoExample(day) => write!(f, "Couldn't find an example in day {day}'s puzzle"),
+            Error::Io(path, e) => write!(f, "Couldn't use {}: {e}", path.display()),
+            Error::History(path, e) => write!(f, "Couldn't read {}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,241 @@
+//! A stand-in for the Advent of Code site that runs on localhost, so the
+//! client can be tested without the real network or a real session
+//!
+//! It serves puzzle pages, inputs and answer responses shaped like the real
+//! site's, and judges answers against the ones it was given.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// One day's puzzle, as the stand-in serves it
+#[derive(Debug, Clone)]
+pub struct MockDay {
+    pub day: u8,
+    pub example: String,
+    pub input: String,
+    pub answers: [String; 2],
+}
+
+#[derive(Debug, Default)]
+struct State {
+    days: Vec<MockDay>,
+    solved: HashSet<(u8, u8)>,
+    rate_limit: Option<u64>,
+    requests: usize,
+}
+
+pub struct MockServer {
+    url: String,
+    server: Arc<Server>,
+    state: Arc<Mutex<State>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts serving `days` on a free port, to anyone with the `session`
+    /// cookie
+    pub fn start(session: &str, days: Vec<MockDay>) -> Self {
+        let server = Arc::new(Server::http("127.0.0.1:0").expect("Couldn't start the mock server"));
+        let url = format!("http://{}", server.server_addr());
+        let state = Arc::new(Mutex::new(State {
+            days,
+            ..State::default()
+        }));
+
+        let thread = {
+            let server = Arc::clone(&server);
+            let state = Arc::clone(&state);
+            let cookie = format!("session={session}");
+
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let mut state = state.lock().unwrap();
+                    state.requests += 1;
+
+                    respond(&mut state, &cookie, request);
+                }
+            })
+        };
+
+        Self {
+            url,
+            server,
+            state,
+            thread: Some(thread),
+        }
+    }
+
+    /// Where to point a [`Client`](crate::Client) with `with_base_url`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// How many requests have come in so far
+    pub fn requests(&self) -> usize {
+        self.state.lock().unwrap().requests
+    }
+
+    /// Turns the next answer away, as if the last one had been too recent
+    pub fn rate_limit_next(&self, wait_secs: u64) {
+        self.state.lock().unwrap().rate_limit = Some(wait_secs);
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn respond(state: &mut State, cookie: &str, mut request: Request) {
+    let logged_in = request
+        .headers()
+        .iter()
+        .any(|h| h.field.equiv("Cookie") && h.value.as_str().contains(cookie));
+
+    if !logged_in {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        let _ = request.respond(Response::from_string(body).with_status_code(400));
+        return;
+    }
+
+    // Paths look like /2022/day/1, /2022/day/1/input or /2022/day/1/answer
+    let url = request.url().to_string();
+    let segments: Vec<&str> = url.trim_start_matches('/').split('/').collect();
+    let day = match segments[..] {
+        [_, "day", day, ..] => day.parse::<u8>().ok(),
+        _ => None,
+    };
+    let Some(puzzle) = day
+        .and_then(|day| state.days.iter().find(|d| d.day == day))
+        .cloned()
+    else {
+        let _ = request.respond(Response::from_string("404 Not Found").with_status_code(404));
+        return;
+    };
+
+    let response = match (request.method(), &segments[3..]) {
+        (Method::Get, []) => html(&format!(
+            "<p>Some story.</p><p>For example:</p><pre><code>{}</code></pre>",
+            escape(&puzzle.example)
+        )),
+        (Method::Get, ["input"]) => Response::from_string(puzzle.input.clone()),
+        (Method::Post, ["answer"]) => {
+            let mut body = String::new();
+            let _ = request.as_reader().read_to_string(&mut body);
+
+            html(&judge(state, &puzzle, &body))
+        }
+        _ => Response::from_string("404 Not Found").with_status_code(404),
+    };
+
+    let _ = request.respond(response);
+}
+
+/// Works out the response to an answer in a form like `level=1&answer=24000`
+fn judge(state: &mut State, puzzle: &MockDay, form: &str) -> String {
+    let field = |name: &str| {
+        form.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| decode(value))
+    };
+
+    let part = field("level").and_then(|level| level.parse::<u8>().ok());
+    let (Some(part @ 1..=2), Some(answer)) = (part, field("answer")) else {
+        return "You don't seem to be solving the right level.".to_string();
+    };
+
+    if state.solved.contains(&(puzzle.day, part)) {
+        return "You don't seem to be solving the right level.  Did you already complete it?"
+            .to_string();
+    }
+
+    if let Some(wait) = state.rate_limit.take() {
+        return format!(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have {}m {}s left to wait.",
+            wait / 60,
+            wait % 60
+        );
+    }
+
+    let correct = &puzzle.answers[part as usize - 1];
+    if answer == *correct {
+        state.solved.insert((puzzle.day, part));
+
+        return "That's the right answer!  You are <em>one gold star</em> closer.".to_string();
+    }
+
+    let hint = match (answer.parse::<i64>(), correct.parse::<i64>()) {
+        (Ok(answer), Ok(correct)) if answer > correct => "; your answer is too high",
+        (Ok(answer), Ok(correct)) if answer < correct => "; your answer is too low",
+        _ => "",
+    };
+
+    format!("That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.")
+}
+
+fn html(article: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    let page = format!(
+        "<!DOCTYPE html><html><body><main><article>{article}</article></main></body></html>"
+    );
+
+    Response::from_string(page)
+        .with_header(Header::from_bytes("Content-Type", "text/html").unwrap())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Undoes the URL encoding of a form value
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_form_values() {
+        assert_eq!(decode("two+words"), "two words");
+        assert_eq!(decode("%23%2A%0A"), "#*\n");
+        assert_eq!(decode("100%"), "100%");
+    }
+}
@@ -0,0 +1,67 @@
+//! Just enough HTML handling to pick what we need out of the site's pages,
+//! which are simple and regular enough not to need a real parser
+
+/// The text of the page's `<article>`, which is where the site puts the
+/// puzzle description and the response to an answer
+pub fn article_text(html: &str) -> String {
+    let article = between(html, "<article", "</article>").unwrap_or(html);
+    let article = article.split_once('>').map_or(article, |(_, rest)| rest);
+
+    text(article)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The first example input in a puzzle description, which comes in the first
+/// code block after the words "for example"
+pub fn example(html: &str) -> Option<String> {
+    let lower = html.to_lowercase();
+    let start = lower.find("for example").unwrap_or(0);
+    let block = between(&html[start..], "<pre><code>", "</code></pre>")?;
+
+    Some(text(block))
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(start)?;
+    let (inside, _) = rest.split_once(end)?;
+
+    Some(inside)
+}
+
+/// Drops the tags from some HTML and decodes the entities the site uses
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_example_after_the_words_for_example() {
+        let html = "<article><p>Some <code>code</code> and <pre><code>not this</code></pre></p>\
+            <p>For example:</p><pre><code>1 &lt; 2\n<em>3</em> &amp; 4\n</code></pre>\
+            <pre><code>or this</code></pre></article>";
+
+        assert_eq!(example(html).as_deref(), Some("1 < 2\n3 & 4\n"));
+        assert_eq!(example("<p>No code here</p>"), None);
+    }
+}
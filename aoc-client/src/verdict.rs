@@ -0,0 +1,146 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::page;
+
+/// What the site made of a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    /// Wrong, without a hint about which way
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// We answered too soon after the last wrong answer, so this one wasn't
+    /// looked at
+    RateLimited {
+        wait_secs: Option<u64>,
+    },
+    /// The part was already solved, so this one wasn't looked at
+    AlreadySolved,
+    /// The site said something we don't recognise, which we keep to show
+    Unrecognised(String),
+}
+
+impl Verdict {
+    /// Works out the verdict from the page the site sends back after an
+    /// answer is submitted
+    pub fn from_page(html: &str) -> Self {
+        let text = page::article_text(html);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited {
+                wait_secs: wait_secs(&text),
+            }
+        } else if text.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if text.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else {
+            Verdict::Unrecognised(text)
+        }
+    }
+
+    /// Whether the answer was actually judged, rather than turned away
+    pub fn is_judged(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer"),
+            Verdict::Wrong => write!(f, "That's not the right answer"),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Verdict::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Verdict::RateLimited {
+                wait_secs: Some(secs),
+            } => write!(f, "Answered too recently, try again in {secs}s"),
+            Verdict::RateLimited { wait_secs: None } => write!(f, "Answered too recently"),
+            Verdict::AlreadySolved => write!(f, "That part is already solved"),
+            Verdict::Unrecognised(text) => write!(f, "The site said: {text}"),
+        }
+    }
+}
+
+/// Reads the wait out of "You have 1m 5s left to wait"
+fn wait_secs(text: &str) -> Option<u64> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .map(|part| {
+            let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+
+            match unit {
+                "h" => Some(number * 60 * 60),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn recognises_every_verdict() {
+        let cases = [
+            (
+                "That's the right answer! You are <em>one gold star</em> closer.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                Verdict::Wrong,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Verdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait.",
+                Verdict::RateLimited { wait_secs: Some(65) },
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                Verdict::AlreadySolved,
+            ),
+        ];
+
+        for (message, verdict) in cases {
+            assert_eq!(Verdict::from_page(&page(message)), verdict, "{message}");
+        }
+
+        assert_eq!(
+            Verdict::from_page(&page("Something <em>new</em>")),
+            Verdict::Unrecognised("Something new".to_string())
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+
+/// A piece of the input that wasn't what we expected to find
+///
+/// `text` is always a slice of the original puzzle input, which is what lets
+/// us work out which line and column it came from later on with
+/// [`Unexpected::locate`]. When something is missing entirely, `text` is the
+/// empty slice right where we expected it to be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unexpected<'a> {
+    pub text: &'a str,
+    pub expected: String,
+}
+
+impl<'a> Unexpected<'a> {
+    pub fn new(text: &'a str, expected: impl Into<String>) -> Self {
+        Self {
+            text,
+            expected: expected.into(),
+        }
+    }
+
+    /// Points at the empty slice just past the end of `text`, for when
+    /// something we needed never showed up
+    pub fn missing_after(text: &'a str, expected: impl Into<String>) -> Self {
+        Self::new(&text[text.len()..], expected)
+    }
+
+    /// Ties this back to its place in `input`, which must be the full puzzle
+    /// input `text` was sliced from
+    pub fn locate(self, day: u8, input: &str) -> ParseError {
+        let start = input.as_ptr() as usize;
+        let offset = (self.text.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| *offset <= input.len());
+        debug_assert!(offset.is_some(), "{:?} isn't part of the input", self.text);
+
+        let before = &input[..offset.unwrap_or(0)];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        ParseError {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: self.text.lines().next().unwrap_or_default().to_string(),
+            expected: self.expected,
+        }
+    }
+}
+
+/// Why a puzzle input couldn't be parsed, and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// Starts from 1, like an editor would show it
+    pub line: usize,
+    /// Counted in characters, starting from 1
+    pub column: usize,
+    /// The offending text, cut off at the end of its line
+    pub text: String,
+    pub expected: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            day,
+            line,
+            column,
+            text,
+            expected,
+        } = self;
+
+        if text.is_empty() {
+            write!(
+                f,
+                "Day {day} input, line {line} column {column}: expected {expected} but found nothing"
+            )
+        } else {
+            write!(
+                f,
+                "Day {day} input, line {line} column {column}: expected {expected} but found {text:?}"
+            )
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a day couldn't answer a part for an input that parsed fine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// A param was set to something the day can't work with
+    BadParam {
+        name: String,
+        value: i64,
+        expected: String,
+    },
+    /// The input doesn't have an answer, like a datastream without a marker
+    NoAnswer(String),
+    /// The day doesn't have a solution for this part yet
+    NotSolved,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::BadParam {
+                name,
+                value,
+                expected,
+            } => write!(f, "{name} can't be {value}, it should be {expected}"),
+            SolveError::NoAnswer(reason) => write!(f, "There's no answer, {reason}"),
+            SolveError::NotSolved => write!(f, "This part hasn't been solved yet"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Anything that can stop an input being answered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => e.fmt(f),
+            Error::Solve(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<SolveError> for Error {
+    fn from(e: SolveError) -> Self {
+        Error::Solve(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_text_in_the_input() {
+        let input = "1000\n2000\n\n3x00\n";
+        let bad = &input[11..15];
+
+        let err = Unexpected::new(bad, "a number").locate(1, input);
+
+        assert_eq!(err.line, 4);
+        assert_eq!(err.column, 1);
+        assert_eq!(err.text, "3x00");
+        assert_eq!(
+            err.to_string(),
+            "Day 1 input, line 4 column 1: expected a number but found \"3x00\""
+        );
+    }
+
+    #[test]
+    fn locates_missing_text_at_the_end_of_a_line() {
+        let input = "A Y\nB\nC Z";
+        let line = input.lines().nth(1).unwrap();
+
+        let err = Unexpected::missing_after(line, "a second column").locate(2, input);
+
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.text, "");
+    }
+
+    #[test]
+    fn columns_count_characters_not_bytes() {
+        let input = "é→x";
+        let bad = &input[input.len() - 1..];
+
+        let err = Unexpected::new(bad, "an arrow").locate(1, input);
+
+        assert_eq!(err.column, 3);
+    }
+}
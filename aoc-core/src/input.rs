@@ -0,0 +1,46 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+/// A puzzle input that is built into a day's crate, like the example from
+/// the puzzle text or the input the solutions were first written against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture {
+    pub name: &'static str,
+    pub input: &'static str,
+}
+
+impl Fixture {
+    pub const fn new(name: &'static str, input: &'static str) -> Self {
+        Self { name, input }
+    }
+}
+
+/// Reads a puzzle input from `path`, or from stdin when there is no path or
+/// the path is `-`
+pub fn read_input(path: Option<&Path>) -> io::Result<String> {
+    match path {
+        Some(path) if path != Path::new("-") => fs::read_to_string(path),
+        _ => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+
+            Ok(input)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_input_from_a_path() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let input = read_input(Some(&path)).unwrap();
+
+        assert!(input.starts_with("[package]"));
+    }
+}
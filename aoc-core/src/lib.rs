@@ -0,0 +1,45 @@
+use std::fmt::Display;
+
+mod error;
+mod input;
+mod params;
+
+pub use error::{Error, ParseError, SolveError, Unexpected};
+pub use input::{read_input, Fixture};
+pub use params::Params;
+
+/// A single day of the advent calendar
+///
+/// Parsing is split out from solving so that tooling (the runner, benchmarks,
+/// answer verification) can time and drive each step on its own, and so both
+/// parts can share a single parse of the input.
+///
+/// Solutions never print. Anything worth saying about how they're getting on
+/// goes through `tracing`, inside spans for the day and part that the runner
+/// opens, and the runner decides what gets shown.
+pub trait Solution {
+    /// The day of December this puzzle was released on
+    const DAY: u8;
+
+    /// The puzzle title, as it appears on the Advent of Code site
+    const NAME: &'static str;
+
+    /// Names of the runtime [`Params`] this day reads, if any
+    const PARAMS: &'static [&'static str] = &[];
+
+    /// The inputs built into this day's crate, which any other input can be
+    /// swapped in for at runtime
+    const FIXTURES: &'static [Fixture] = &[];
+
+    /// The puzzle input after it has been parsed
+    type Input;
+
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_1(input: &Self::Input, params: &Params) -> Result<Self::Part1, SolveError>;
+
+    fn part_2(input: &Self::Input, params: &Params) -> Result<Self::Part2, SolveError>;
+}
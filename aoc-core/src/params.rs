@@ -0,0 +1,95 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use crate::SolveError;
+
+/// Extra knobs some days need that aren't part of the puzzle input itself,
+/// like the row day 15 looks at or how many tails the rope in day 9 has
+///
+/// Days fall back to the value the puzzle asks for when a param isn't set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, i64>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: impl Into<String>, value: i64) -> Self {
+        self.set(name, value);
+
+        self
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: i64) {
+        self.values.insert(name.into(), value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<i64> {
+        self.values.get(name).copied()
+    }
+
+    pub fn get_or(&self, name: &str, default: i64) -> i64 {
+        self.get(name).unwrap_or(default)
+    }
+
+    /// Like [`Params::get_or`], for params the day can only work with when
+    /// they're inside `range`
+    pub fn get_within(
+        &self,
+        name: &str,
+        default: i64,
+        range: RangeInclusive<i64>,
+    ) -> Result<i64, SolveError> {
+        let value = self.get_or(name, default);
+
+        if range.contains(&value) {
+            Ok(value)
+        } else {
+            Err(SolveError::BadParam {
+                name: name.to_string(),
+                value,
+                expected: format!("from {} to {}", range.start(), range.end()),
+            })
+        }
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_default() {
+        let params = Params::new().with("row", 10);
+
+        assert_eq!(params.get_or("row", 2_000_000), 10);
+        assert_eq!(params.get_or("max", 4_000_000), 4_000_000);
+    }
+
+    #[test]
+    fn rejects_values_out_of_range() {
+        let params = Params::new().with("window_size", 0);
+
+        assert_eq!(params.get_within("max", 20, 0..=i64::MAX), Ok(20));
+        assert_eq!(
+            params
+                .get_within("window_size", 4, 1..=26)
+                .unwrap_err()
+                .to_string(),
+            "window_size can't be 0, it should be from 1 to 26"
+        );
+    }
+
+    #[test]
+    fn names_are_sorted() {
+        let params = Params::new().with("row", 10).with("max", 20);
+
+        assert_eq!(params.names().collect::<Vec<_>>(), vec!["max", "row"]);
+    }
+}
@@ -0,0 +1,480 @@
+//! Every day's solution, behind one type that doesn't need to know the day's
+//! own types
+//!
+//! This is the only list of days. The runner, the benchmarks and the C
+//! library all find their days here, so a new day is added once, to
+//! [`DAYS`] and this crate's dependencies.
+
+use std::fmt;
+
+use aoc_core::{Fixture, Params, ParseError, Solution, SolveError};
+use aoc_vis::{Animation, Palette, Visualise};
+use tracing::{debug_span, info_span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    DayNotSolved(u8),
+    PartNotSolved { day: u8, part: u8 },
+    UnknownParam { day: u8, name: String },
+    UnknownFixture { day: u8, name: String },
+    CantDraw(u8),
+    Parse(ParseError),
+    Solve { day: u8, error: SolveError },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::DayNotSolved(day) => write!(f, "There is no solution for day {day}"),
+            Error::PartNotSolved { day, part } => {
+                write!(f, "There is no solution for day {day} part {part}")
+            }
+            Error::UnknownParam { day, name } => {
+                write!(f, "Day {day} doesn't take a {name:?} param")
+            }
+            Error::UnknownFixture { day, name } => {
+                write!(f, "Day {day} doesn't have a {name:?} input built in")
+            }
+            Error::CantDraw(day) => write!(f, "Day {day} doesn't know how to draw itself"),
+            Error::Parse(e) => e.fmt(f),
+            Error::Solve { day, error } => write!(f, "Day {day}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// One step of solving an input, see [`Day::steps`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Step {
+    pub fn name(self) -> &'static str {
+        match self {
+            Step::Parse => "parse",
+            Step::Part1 => "part_1",
+            Step::Part2 => "part_2",
+        }
+    }
+}
+
+/// Runs a step handed to it by [`Day::steps`], as many times as it likes
+pub type Runner<'a> = &'a mut dyn FnMut(Step, &mut dyn FnMut());
+
+/// Everything the tools need to know about a day, without its types
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub name: &'static str,
+    pub params: &'static [&'static str],
+    pub fixtures: &'static [Fixture],
+    run: fn(u8, &str, &Params) -> Result<String, Error>,
+    parse: fn(&str) -> Result<(), ParseError>,
+    steps: Steps,
+    draw: Option<Draw>,
+}
+
+type Steps = fn(&str, &Params, Runner) -> Result<[Option<String>; 2], Error>;
+type Draw = fn(&str, &Params, &mut Animation) -> Result<Palette, Error>;
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            name: S::NAME,
+            params: S::PARAMS,
+            fixtures: S::FIXTURES,
+            run: run::<S>,
+            parse: |input| S::parse(input).map(drop),
+            steps: steps::<S>,
+            draw: None,
+        }
+    }
+
+    /// A day that can draw what it's doing too
+    const fn drawn<S: Visualise>() -> Self {
+        Self {
+            draw: Some(draw::<S>),
+            ..Self::of::<S>()
+        }
+    }
+
+    pub fn solve(&self, part: u8, input: &str, params: &Params) -> Result<String, Error> {
+        (self.run)(part, input, params)
+    }
+
+    /// Parses the input without solving anything, to see whether it could
+    /// be this day's
+    pub fn parse(&self, input: &str) -> Result<(), ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Solves both parts from one parse of the input, handing parsing and
+    /// each part to `runner` in turn so it can time them. The answers are
+    /// whatever the last run of each part gave, and `None` for a part the
+    /// runner didn't run at all
+    pub fn steps(
+        &self,
+        input: &str,
+        params: &Params,
+        runner: Runner,
+    ) -> Result<[Option<String>; 2], Error> {
+        (self.steps)(input, params, runner)
+    }
+
+    /// Records the day's drawing into `animation`, and hands back the palette
+    /// that suits it
+    pub fn draw(
+        &self,
+        input: &str,
+        params: &Params,
+        animation: &mut Animation,
+    ) -> Result<Palette, Error> {
+        let draw = self.draw.ok_or(Error::CantDraw(self.day))?;
+
+        draw(input, params, animation)
+    }
+
+    /// One of the inputs built into the day's crate
+    pub fn fixture(&self, name: &str) -> Result<&'static str, Error> {
+        self.fixtures
+            .iter()
+            .find(|fixture| fixture.name == name)
+            .map(|fixture| fixture.input)
+            .ok_or_else(|| Error::UnknownFixture {
+                day: self.day,
+                name: name.to_string(),
+            })
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::of::<calorie_counting::CalorieCounting>(),
+    Day::of::<rock_paper_scissors::RockPaperScissors>(),
+    Day::of::<rucksack_reogranization::RucksackReorganization>(),
+    Day::of::<camp_cleanup::CampCleanup>(),
+    Day::of::<supply_stacks::SupplyStacks>(),
+    Day::of::<tuning_trouble::TuningTrouble>(),
+    Day::of::<no_space_left_on_device::NoSpaceLeftOnDevice>(),
+    Day::of::<treetop_tree_house::TreetopTreeHouse>(),
+    Day::drawn::<rope_bridge::RopeBridge>(),
+    Day::drawn::<cathod_ray_tube::CathodeRayTube>(),
+    Day::of::<monkey_in_the_middle::MonkeyInTheMiddle>(),
+    Day::drawn::<hill_climbing_algorithm::HillClimbingAlgorithm>(),
+    Day::of::<distress_signal::DistressSignal>(),
+    Day::drawn::<regolith_reservoir::RegolithReservoir>(),
+    Day::drawn::<beacon_exclusion_zone::BeaconExclusionZone>(),
+    Day::of::<proboscidea_volcanium::ProboscideaVolcanium>(),
+    Day::drawn::<pyroclastic_flow::PyroclasticFlow>(),
+    Day::of::<boiling_boulders::BoilingBoulders>(),
+    Day::of::<not_enough_minerals::NotEnoughMinerals>(),
+    Day::of::<grove_positioning_system::GrovePositioningSystem>(),
+];
+
+pub fn find(day: u8) -> Result<&'static Day, Error> {
+    DAYS.iter()
+        .find(|d| d.day == day)
+        .ok_or(Error::DayNotSolved(day))
+}
+
+/// Runs the solution for the given day and part, and returns the answer
+/// formatted the way we would submit it
+pub fn solve(day: u8, part: u8, input: &str, params: &Params) -> Result<String, Error> {
+    find(day)?.solve(part, input, params)
+}
+
+fn check_params<S: Solution>(params: &Params) -> Result<(), Error> {
+    match params.names().find(|name| !S::PARAMS.contains(name)) {
+        Some(name) => Err(Error::UnknownParam {
+            day: S::DAY,
+            name: name.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn run<S: Solution>(part: u8, input: &str, params: &Params) -> Result<String, Error> {
+    check_params::<S>(params)?;
+
+    if !(1..=2).contains(&part) {
+        return Err(Error::PartNotSolved { day: S::DAY, part });
+    }
+
+    let _day = day_span::<S>().entered();
+    let parsed = debug_span!("parse").in_scope(|| S::parse(input))?;
+    let _part = info_span!("part", part).entered();
+
+    let answer = if part == 1 {
+        S::part_1(&parsed, params).map(|answer| answer.to_string())
+    } else {
+        S::part_2(&parsed, params).map(|answer| answer.to_string())
+    };
+
+    answer.map_err(solve_error::<S>)
+}
+
+fn solve_error<S: Solution>(error: SolveError) -> Error {
+    Error::Solve { day: S::DAY, error }
+}
+
+/// Everything a day logs happens inside this, so each line says which day
+/// it came from
+fn day_span<S: Solution>() -> tracing::Span {
+    info_span!("day", day = S::DAY, title = S::NAME)
+}
+
+fn steps<S: Solution>(
+    input: &str,
+    params: &Params,
+    runner: Runner,
+) -> Result<[Option<String>; 2], Error> {
+    check_params::<S>(params)?;
+
+    let _day = day_span::<S>().entered();
+
+    // The parts can't go without a parse, so we parse once more ourselves if
+    // the runner skipped it
+    let mut parsed = None;
+    debug_span!("parse").in_scope(|| runner(Step::Parse, &mut || parsed = Some(S::parse(input))));
+    let parsed = parsed.unwrap_or_else(|| S::parse(input))?;
+
+    let mut part_1 = None;
+    info_span!("part", part = 1).in_scope(|| {
+        runner(Step::Part1, &mut || {
+            part_1 = Some(S::part_1(&parsed, params))
+        })
+    });
+
+    let mut part_2 = None;
+    info_span!("part", part = 2).in_scope(|| {
+        runner(Step::Part2, &mut || {
+            part_2 = Some(S::part_2(&parsed, params))
+        })
+    });
+
+    let part_1 = part_1.transpose().map_err(solve_error::<S>)?;
+    let part_2 = part_2.transpose().map_err(solve_error::<S>)?;
+
+    Ok([
+        part_1.map(|answer| answer.to_string()),
+        part_2.map(|answer| answer.to_string()),
+    ])
+}
+
+fn draw<S: Visualise>(
+    input: &str,
+    params: &Params,
+    animation: &mut Animation,
+) -> Result<Palette, Error> {
+    check_params::<S>(params)?;
+
+    let _day = day_span::<S>().entered();
+    let parsed = debug_span!("parse").in_scope(|| S::parse(input))?;
+    info_span!("draw")
+        .in_scope(|| S::draw(&parsed, params, animation))
+        .map_err(solve_error::<S>)?;
+
+    Ok(S::PALETTE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_day_1_example() {
+        let input = include_str!("../../01-calorie-counting/src/example.input");
+        let ans = solve(1, 2, input, &Params::default());
+
+        assert_eq!(ans, Ok("45000".to_string()));
+    }
+
+    #[test]
+    fn passes_params_through_to_day_15() {
+        let input = include_str!("../../15-beacon-exclusion-zone/src/example.input");
+        let params = Params::new().with("row", 10).with("max", 20);
+
+        assert_eq!(solve(15, 1, input, &params), Ok("26".to_string()));
+        assert_eq!(solve(15, 2, input, &params), Ok("56000011".to_string()));
+    }
+
+    #[test]
+    fn draws_the_days_that_can() {
+        let params = Params::new().with("max", 20);
+
+        for day in [9, 10, 12, 14, 15, 17] {
+            let day = find(day).unwrap();
+            let input = day.fixture("example").unwrap();
+            let params = if day.day == 15 {
+                &params
+            } else {
+                &Params::new()
+            };
+            let mut animation = Animation::new();
+
+            assert!(day.draw(input, params, &mut animation).is_ok());
+            assert!(animation.last().is_some(), "Day {} drew nothing", day.day);
+        }
+
+        let mut animation = Animation::new();
+        assert_eq!(
+            find(1).unwrap().draw("", &Params::new(), &mut animation),
+            Err(Error::CantDraw(1))
+        );
+    }
+
+    /// Somewhere for logs to go that a test can read back
+    #[derive(Clone, Default)]
+    struct Logs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn logs_happen_inside_the_day_and_part() {
+        let logs = Logs::default();
+        let subscriber = {
+            let logs = logs.clone();
+            tracing_subscriber::fmt()
+                .with_max_level(tracing::Level::DEBUG)
+                .with_ansi(false)
+                .with_writer(move || logs.clone())
+                .finish()
+        };
+
+        let day = find(11).unwrap();
+        let input = day.fixture("example").unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            assert_eq!(
+                day.solve(2, input, &Params::new()),
+                Ok("2713310158".to_string())
+            )
+        });
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let progress = logs
+            .lines()
+            .find(|line| line.contains("Monkeys still throwing"));
+
+        assert!(
+            progress
+                .is_some_and(|line| line.contains("day{day=11") && line.contains("part{part=2}")),
+            "{logs}"
+        );
+    }
+
+    #[test]
+    fn unknown_days_and_parts() {
+        let params = Params::default();
+
+        assert_eq!(solve(26, 1, "", &params), Err(Error::DayNotSolved(26)));
+        assert_eq!(
+            solve(1, 3, "", &params),
+            Err(Error::PartNotSolved { day: 1, part: 3 })
+        );
+    }
+
+    #[test]
+    fn reports_where_the_input_went_wrong() {
+        let input = "1000\n2000\n\nthree thousand\n";
+
+        let Err(Error::Parse(e)) = solve(1, 1, input, &Params::default()) else {
+            panic!("Expected a parse error");
+        };
+
+        assert_eq!((e.day, e.line, e.column), (1, 4, 1));
+        assert_eq!(e.text, "three");
+    }
+
+    #[test]
+    fn finds_built_in_fixtures() {
+        let day = find(9).unwrap();
+
+        assert_eq!(
+            day.fixture("larger_example")
+                .map(|input| input.lines().count()),
+            Ok(8)
+        );
+        assert_eq!(
+            day.fixture("mine"),
+            Err(Error::UnknownFixture {
+                day: 9,
+                name: "mine".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_params_the_day_does_not_read() {
+        let params = Params::new().with("row", 10);
+
+        assert_eq!(
+            solve(1, 1, "", &params),
+            Err(Error::UnknownParam {
+                day: 1,
+                name: "row".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn hands_each_step_to_the_runner() {
+        let day = find(1).unwrap();
+        let input = day.fixture("example").unwrap();
+        let mut runs = vec![];
+
+        let answers = day.steps(input, &Params::new(), &mut |step, run| {
+            run();
+            run();
+            runs.push(step);
+        });
+
+        assert_eq!(
+            answers,
+            Ok([Some("24000".to_string()), Some("45000".to_string())])
+        );
+        assert_eq!(runs, [Step::Parse, Step::Part1, Step::Part2]);
+
+        // Parts the runner skips aren't solved
+        let answers = day.steps(input, &Params::new(), &mut |_, _| {});
+        assert_eq!(answers, Ok([None, None]));
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        for day in DAYS {
+            for seed in 0..2 {
+                let Some(input) = aoc_gen::generate(day.day, 10, seed) else {
+                    continue;
+                };
+
+                for part in 1..=2 {
+                    let ans = day.solve(part, &input, &Params::default());
+
+                    assert!(
+                        ans.is_ok(),
+                        "Day {} part {part} with seed {seed}: {ans:?}",
+                        day.day
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Prints the C header for the library, to save over `include/aoc.h` whenever
+//! the functions `src/lib.rs` exports change
+//!
+//! ```text
+//! cargo run -p aoc-ffi --example header > aoc-ffi/include/aoc.h
+//! ```
+
+#[path = "../src/header.rs"]
+mod header;
+
+fn main() {
+    print!("{}", header::generate());
+}
@@ -0,0 +1,76 @@
+// Generated from aoc-ffi/src/lib.rs by
+// `cargo run -p aoc-ffi --example header > aoc-ffi/include/aoc.h`. Don't edit
+// it by hand, change the Rust and generate it again instead
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+// How a call went. Anything but `Ok` comes with a message saying what went
+// wrong in place of the answer
+typedef enum {
+  AOC_STATUS_OK = 0,
+  // There's no solution for that day yet
+  AOC_STATUS_UNKNOWN_DAY = 1,
+  // Days only have parts 1 and 2
+  AOC_STATUS_UNKNOWN_PART = 2,
+  // The day doesn't read one of the params
+  AOC_STATUS_UNKNOWN_PARAM = 3,
+  // A pointer was null, or some text wasn't UTF-8
+  AOC_STATUS_INVALID_ARGUMENT = 4,
+  // The input isn't in the shape the day expects. The message says where
+  AOC_STATUS_PARSE_ERROR = 5,
+  // The solution panicked on the input
+  AOC_STATUS_PANICKED = 6,
+  // One of the params is set to something the day can't work with
+  AOC_STATUS_BAD_PARAM = 7,
+  // The input parsed, but has no answer, or the day can't answer that
+  // part yet
+  AOC_STATUS_NO_ANSWER = 8,
+} AocStatus;
+
+// One of the extra knobs some days take, like the row day 15 looks at
+typedef struct {
+  // A NUL terminated, UTF-8 name
+  const char *name;
+  int64_t value;
+} AocParam;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Solves `part` of `day` for the `input_len` bytes of UTF-8 text at `input`,
+// with `params_len` params from `params`
+//
+// Sets `*answer` to the answer on success, or to a message explaining what
+// went wrong otherwise. Either way it's a NUL terminated string to free with
+// `aoc_free_string`. `answer` is only left alone when it's null itself.
+//
+// # Safety
+//
+// `input` must point to `input_len` readable bytes, and `params` to
+// `params_len` params whose names are NUL terminated. Either can be null
+// when its length is 0.
+AocStatus aoc_solve(uint8_t day,
+                    uint8_t part,
+                    const uint8_t *input,
+                    size_t input_len,
+                    const AocParam *params,
+                    size_t params_len,
+                    char **answer);
+
+// Frees a string handed back by this library. Does nothing with null
+//
+// # Safety
+//
+// `string` must have come from this library, and not been freed already
+void aoc_free_string(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_H */
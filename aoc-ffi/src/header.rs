@@ -0,0 +1,21 @@
+//! Writes the C header for the library from its Rust source, with cbindgen
+//! set up by `cbindgen.toml`
+
+use std::path::Path;
+
+/// The header declaring everything `src/lib.rs` exports
+pub fn generate() -> String {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(dir.join("cbindgen.toml"))
+        .unwrap_or_else(|e| panic!("cbindgen.toml doesn't make sense: {e}"));
+
+    let mut header = vec![];
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(dir.join("src/lib.rs"))
+        .generate()
+        .unwrap_or_else(|e| panic!("Couldn't generate the header: {e}"))
+        .write(&mut header);
+
+    String::from_utf8(header).expect("cbindgen writes UTF-8")
+}
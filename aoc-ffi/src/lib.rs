@@ -0,0 +1,286 @@
+//! A C ABI for every day's solution, so tools that aren't written in Rust can
+//! link to them
+//!
+//! `include/aoc.h` declares everything exported here. It's generated from this
+//! file with cbindgen by the `header` example, and a test fails if it drifts
+//! from the real signatures.
+//! Strings handed back belong to the caller, who gives them back with
+//! `aoc_free_string` once they're done.
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice, str,
+};
+
+use aoc_core::{Params, SolveError};
+use aoc_days::Error;
+
+#[cfg(test)]
+mod header;
+
+/// How a call went. Anything but `Ok` comes with a message saying what went
+/// wrong in place of the answer
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    /// There's no solution for that day yet
+    UnknownDay = 1,
+    /// Days only have parts 1 and 2
+    UnknownPart = 2,
+    /// The day doesn't read one of the params
+    UnknownParam = 3,
+    /// A pointer was null, or some text wasn't UTF-8
+    InvalidArgument = 4,
+    /// The input isn't in the shape the day expects. The message says where
+    ParseError = 5,
+    /// The solution panicked on the input
+    Panicked = 6,
+    /// One of the params is set to something the day can't work with
+    BadParam = 7,
+    /// The input parsed, but has no answer, or the day can't answer that
+    /// part yet
+    NoAnswer = 8,
+}
+
+/// One of the extra knobs some days take, like the row day 15 looks at
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct AocParam {
+    /// A NUL terminated, UTF-8 name
+    pub name: *const c_char,
+    pub value: i64,
+}
+
+/// Solves `part` of `day` for the `input_len` bytes of UTF-8 text at `input`,
+/// with `params_len` params from `params`
+///
+/// Sets `*answer` to the answer on success, or to a message explaining what
+/// went wrong otherwise. Either way it's a NUL terminated string to free with
+/// `aoc_free_string`. `answer` is only left alone when it's null itself.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, and `params` to
+/// `params_len` params whose names are NUL terminated. Either can be null
+/// when its length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    params: *const AocParam,
+    params_len: usize,
+    answer: *mut *mut c_char,
+) -> AocStatus {
+    if answer.is_null() {
+        return AocStatus::InvalidArgument;
+    }
+
+    let (status, text) = match solve(day, part, input, input_len, params, params_len) {
+        Ok(answer) => (AocStatus::Ok, answer),
+        Err(failure) => failure,
+    };
+
+    *answer = into_c_string(text);
+
+    status
+}
+
+/// Frees a string handed back by this library. Does nothing with null
+///
+/// # Safety
+///
+/// `string` must have come from this library, and not been freed already
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+unsafe fn solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    params: *const AocParam,
+    params_len: usize,
+) -> Result<String, (AocStatus, String)> {
+    let invalid = |message: &str| (AocStatus::InvalidArgument, message.to_string());
+
+    let input = str::from_utf8(borrow(input, input_len).ok_or_else(|| invalid("input is null"))?)
+        .map_err(|_| invalid("The input has to be UTF-8 text"))?;
+
+    let mut parsed = Params::new();
+    for param in borrow(params, params_len).ok_or_else(|| invalid("params is null"))? {
+        if param.name.is_null() {
+            return Err(invalid("A param's name is null"));
+        }
+        let name = CStr::from_ptr(param.name)
+            .to_str()
+            .map_err(|_| invalid("Param names have to be UTF-8 text"))?;
+
+        parsed.set(name, param.value);
+    }
+
+    let day = aoc_days::find(day).map_err(|e| (AocStatus::UnknownDay, e.to_string()))?;
+
+    // Unwinding out of an extern "C" function aborts the whole process, and
+    // a solution that chokes on a strange input shouldn't take its caller
+    // down with it
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| day.solve(part, input, &parsed)))
+        .map_err(|payload| {
+            let reason = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("no reason given");
+
+            (
+                AocStatus::Panicked,
+                format!("Day {} part {part} panicked: {reason}", day.day),
+            )
+        })?;
+
+    solved.map_err(|e| {
+        let status = match e {
+            Error::DayNotSolved(_) => AocStatus::UnknownDay,
+            Error::PartNotSolved { .. } => AocStatus::UnknownPart,
+            Error::UnknownParam { .. } => AocStatus::UnknownParam,
+            Error::Parse(_) => AocStatus::ParseError,
+            Error::Solve { ref error, .. } => match error {
+                SolveError::BadParam { .. } => AocStatus::BadParam,
+                SolveError::NoAnswer(_) | SolveError::NotSolved => AocStatus::NoAnswer,
+            },
+            // Solving never looks at the built-in inputs or draws anything
+            Error::UnknownFixture { .. } | Error::CantDraw(_) => AocStatus::InvalidArgument,
+        };
+
+        (status, e.to_string())
+    })
+}
+
+/// Borrows `len` things from `data`, which is allowed to be null when there
+/// aren't any
+unsafe fn borrow<'a, T>(data: *const T, len: usize) -> Option<&'a [T]> {
+    match (data.is_null(), len) {
+        (true, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(slice::from_raw_parts(data, len)),
+    }
+}
+
+fn into_c_string(text: String) -> *mut c_char {
+    // Parse errors quote the input, which could have a NUL in it
+    let text = text.replace('\0', "\u{FFFD}");
+
+    CString::new(text)
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Calls `aoc_solve` the way C would, and takes ownership of the result
+    fn call(day: u8, part: u8, input: &str, params: &[(&CStr, i64)]) -> (AocStatus, String) {
+        let params: Vec<_> = params
+            .iter()
+            .map(|(name, value)| AocParam {
+                name: name.as_ptr(),
+                value: *value,
+            })
+            .collect();
+        let mut answer = ptr::null_mut();
+
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                params.as_ptr(),
+                params.len(),
+                &mut answer,
+            )
+        };
+        let text = unsafe { CStr::from_ptr(answer) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { aoc_free_string(answer) };
+
+        (status, text)
+    }
+
+    #[test]
+    fn solves_every_day() {
+        let input = include_str!("../../01-calorie-counting/src/example.input");
+        assert_eq!(call(1, 2, input, &[]), (AocStatus::Ok, "45000".into()));
+
+        let input = include_str!("../../15-beacon-exclusion-zone/src/example.input");
+        let params = [(c"row", 10), (c"max", 20)];
+        assert_eq!(call(15, 1, input, &params), (AocStatus::Ok, "26".into()));
+
+        assert_eq!(
+            aoc_days::DAYS.iter().map(|day| day.day).collect::<Vec<_>>(),
+            (1..=20).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn explains_what_went_wrong() {
+        assert_eq!(call(26, 1, "", &[]).0, AocStatus::UnknownDay);
+        assert_eq!(call(1, 3, "", &[]).0, AocStatus::UnknownPart);
+        assert_eq!(
+            call(1, 1, "", &[(c"row", 10)]),
+            (
+                AocStatus::UnknownParam,
+                "Day 1 doesn't take a \"row\" param".into()
+            )
+        );
+
+        let (status, message) = call(1, 1, "1000\nlots\n", &[]);
+        assert_eq!(status, AocStatus::ParseError);
+        assert!(message.contains("lots"), "{message}");
+
+        // There is no start-of-packet marker to find
+        assert_eq!(call(6, 1, "aaaaaaaa\n", &[]).0, AocStatus::NoAnswer);
+        assert_eq!(
+            call(6, 1, "abcd\n", &[(c"window_size", 0)]),
+            (
+                AocStatus::BadParam,
+                "Day 6: window_size can't be 0, it should be from 1 to 26".into()
+            )
+        );
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        assert!(
+            include_str!("../include/aoc.h") == header::generate(),
+            "include/aoc.h is out of date, run \
+             `cargo run -p aoc-ffi --example header > aoc-ffi/include/aoc.h`"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_pointers_and_text() {
+        let mut answer = ptr::null_mut();
+        let status = unsafe { aoc_solve(1, 1, ptr::null(), 4, ptr::null(), 0, &mut answer) };
+        assert_eq!(status, AocStatus::InvalidArgument);
+        unsafe { aoc_free_string(answer) };
+
+        let input = [0xff, b'\n'];
+        let status = unsafe { aoc_solve(1, 1, input.as_ptr(), 2, ptr::null(), 0, &mut answer) };
+        assert_eq!(status, AocStatus::InvalidArgument);
+        unsafe { aoc_free_string(answer) };
+
+        let status = unsafe { aoc_solve(1, 1, ptr::null(), 0, ptr::null(), 0, ptr::null_mut()) };
+        assert_eq!(status, AocStatus::InvalidArgument);
+    }
+}
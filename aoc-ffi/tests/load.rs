@@ -0,0 +1,120 @@
+//! Loads the built library the way another language would, rather than
+//! linking to it as a Rust crate
+
+use std::{
+    env,
+    ffi::{c_char, CStr},
+    path::PathBuf,
+    process::Command,
+    ptr,
+};
+
+use libloading::{Library, Symbol};
+
+#[repr(C)]
+struct AocParam {
+    name: *const c_char,
+    value: i64,
+}
+
+type Solve =
+    unsafe extern "C" fn(u8, u8, *const u8, usize, *const AocParam, usize, *mut *mut c_char) -> i32;
+type Free = unsafe extern "C" fn(*mut c_char);
+
+/// Cargo builds the library next to the test binaries
+fn library_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().to_path_buf()
+}
+
+fn library() -> Library {
+    let path = library_dir().join(libloading::library_filename("aoc_ffi"));
+
+    unsafe { Library::new(&path) }.unwrap_or_else(|e| panic!("Couldn't load {path:?}: {e}"))
+}
+
+#[test]
+fn solves_through_the_loaded_library() {
+    let library = library();
+    let solve: Symbol<Solve> = unsafe { library.get(b"aoc_solve") }.unwrap();
+    let free: Symbol<Free> = unsafe { library.get(b"aoc_free_string") }.unwrap();
+
+    let input = include_str!("../../15-beacon-exclusion-zone/src/example.input");
+    let params = [
+        AocParam {
+            name: c"row".as_ptr(),
+            value: 10,
+        },
+        AocParam {
+            name: c"max".as_ptr(),
+            value: 20,
+        },
+    ];
+
+    for (part, expected) in [(1, "26"), (2, "56000011")] {
+        let mut answer = ptr::null_mut();
+        let status = unsafe {
+            solve(
+                15,
+                part,
+                input.as_ptr(),
+                input.len(),
+                params.as_ptr(),
+                params.len(),
+                &mut answer,
+            )
+        };
+
+        assert_eq!(status, 0);
+        assert_eq!(unsafe { CStr::from_ptr(answer) }.to_str(), Ok(expected));
+        unsafe { free(answer) };
+    }
+
+    let mut message = ptr::null_mut();
+    let status = unsafe { solve(26, 1, ptr::null(), 0, ptr::null(), 0, &mut message) };
+    assert_eq!(status, 1);
+    assert_eq!(
+        unsafe { CStr::from_ptr(message) }.to_str(),
+        Ok("There is no solution for day 26")
+    );
+    unsafe { free(message) };
+}
+
+/// Builds a small C program against the generated header and runs it, when
+/// there's a C compiler around to do it with
+#[test]
+fn works_from_c() {
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let out = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("solve");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+
+    let compiled = Command::new(&compiler)
+        .arg(manifest.join("tests/solve.c"))
+        .arg("-I")
+        .arg(manifest.join("include"))
+        .args(["-std=c99", "-Wall", "-Werror", "-o"])
+        .arg(&out)
+        .arg("-L")
+        .arg(library_dir())
+        .arg("-laoc_ffi")
+        .status();
+
+    match compiled {
+        Ok(status) => assert!(status.success(), "{compiler} couldn't build solve.c"),
+        Err(e) => {
+            eprintln!("Skipping, couldn't run {compiler}: {e}");
+            return;
+        }
+    }
+
+    let input = include_str!("../../15-beacon-exclusion-zone/src/example.input");
+    let output = Command::new(&out)
+        .arg(input)
+        .env("LD_LIBRARY_PATH", library_dir())
+        .env("DYLD_LIBRARY_PATH", library_dir())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0 26\n");
+}
@@ -0,0 +1,24 @@
+// Solves day 15 part 1 of the example from C, using nothing but the header
+// and the library
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s INPUT\n", argv[0]);
+        return 2;
+    }
+
+    AocParam params[] = {{"row", 10}};
+    char *answer = NULL;
+    AocStatus status = aoc_solve(15, 1, (const uint8_t *)argv[1], strlen(argv[1]),
+                                 params, 1, &answer);
+
+    printf("%d %s\n", status, answer);
+    aoc_free_string(answer);
+
+    return status == AOC_STATUS_OK ? 0 : 1;
+}
@@ -0,0 +1,22 @@
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// `size` elves, each carrying a handful of snacks. Part 2 wants the top
+/// three elves, so there are always at least three
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(3))
+        .map(|_| {
+            let mut elf = String::new();
+            for _ in 0..rng.gen_range(1..=15) {
+                writeln!(elf, "{}", rng.gen_range(1000..=70_000)).unwrap();
+            }
+
+            elf
+        })
+        .collect();
+
+    elves.join("\n")
+}
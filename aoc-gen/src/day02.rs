@@ -0,0 +1,19 @@
+use std::fmt::Write;
+
+use rand::seq::SliceRandom;
+
+use crate::Rng;
+
+/// `size` rounds of the strategy guide
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let theirs = ['A', 'B', 'C'].choose(rng).unwrap();
+        let ours = ['X', 'Y', 'Z'].choose(rng).unwrap();
+
+        writeln!(input, "{theirs} {ours}").unwrap();
+    }
+
+    input
+}
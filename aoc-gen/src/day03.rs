@@ -0,0 +1,53 @@
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+/// `size` rucksacks, rounded up to a whole number of elf groups
+///
+/// Each rucksack has exactly one item in both compartments, and each group of
+/// three has exactly one item, its badge, that all of them carry.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        items.shuffle(rng);
+        let badge = items.pop().unwrap();
+
+        // Each elf in the group leaves out a different third of the other
+        // items, so nothing but the badge can end up in all three rucksacks
+        let thirds: Vec<&[char]> = items.chunks(items.len() / 3).collect();
+
+        for left_out in 0..3 {
+            let mut allowed: Vec<char> = thirds
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != left_out)
+                .flat_map(|(_, third)| third.iter().copied())
+                .collect();
+            allowed.shuffle(rng);
+
+            let (shared, rest) = allowed.split_first().unwrap();
+            let (first_only, second_only) = rest.split_at(rest.len() / 2);
+
+            let half = rng.gen_range(2..=16);
+            let mut first = vec![*shared];
+            let mut second = vec![*shared];
+            first.extend((1..half).map(|_| *first_only.choose(rng).unwrap()));
+            second.extend((1..half).map(|_| *second_only.choose(rng).unwrap()));
+
+            // The badge takes the place of an item that isn't the shared one
+            let compartment = if rng.gen() { &mut first } else { &mut second };
+            compartment[rng.gen_range(1..half)] = badge;
+
+            first.shuffle(rng);
+            second.shuffle(rng);
+
+            input.extend(first);
+            input.extend(second);
+            input.push('\n');
+        }
+    }
+
+    input
+}
@@ -0,0 +1,25 @@
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// `size` pairs of elves
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut range = || {
+            let start = rng.gen_range(1..=99);
+            let end = rng.gen_range(start..=99);
+
+            format!("{start}-{end}")
+        };
+
+        let left = range();
+        let right = range();
+        writeln!(input, "{left},{right}").unwrap();
+    }
+
+    input
+}
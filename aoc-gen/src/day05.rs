@@ -0,0 +1,61 @@
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// A crane yard with `size` rearrangements
+///
+/// We play the moves out as we make them up, so a move never takes more
+/// crates than its stack has, and every stack still has a crate on top at
+/// the end.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let stack_count = rng.gen_range(3..=9);
+    let mut heights: Vec<usize> = (0..stack_count)
+        .map(|_| rng.gen_range(2..=8 + size / 50))
+        .collect();
+
+    let mut input = String::new();
+
+    // The drawing goes from the top of the tallest stack down
+    let tallest = *heights.iter().max().unwrap();
+    for level in (0..tallest).rev() {
+        let row: Vec<String> = heights
+            .iter()
+            .map(|height| {
+                if level < *height {
+                    format!("[{}]", rng.gen_range('A'..='Z'))
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect();
+
+        writeln!(input, "{}", row.join(" ")).unwrap();
+    }
+
+    let ids: Vec<String> = (1..=stack_count).map(|id| format!(" {id} ")).collect();
+    writeln!(input, "{}", ids.join(" ")).unwrap();
+    writeln!(input).unwrap();
+
+    for _ in 0..size {
+        // There are at least twice as many crates as stacks, so some stack
+        // always has a crate to spare
+        let from = loop {
+            let from = rng.gen_range(0..stack_count);
+
+            if heights[from] > 1 {
+                break from;
+            }
+        };
+        let to = (from + rng.gen_range(1..stack_count)) % stack_count;
+        let count = rng.gen_range(1..heights[from]);
+
+        heights[from] -= count;
+        heights[to] += count;
+
+        writeln!(input, "move {count} from {} to {}", from + 1, to + 1).unwrap();
+    }
+
+    input
+}
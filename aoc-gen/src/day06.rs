@@ -0,0 +1,24 @@
+use rand::seq::SliceRandom;
+
+use crate::Rng;
+
+/// A datastream `size` characters long
+///
+/// Everything up to the last 14 characters is made from just three letters,
+/// so neither marker turns up until the very end and the whole stream has to
+/// be scanned.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    letters.shuffle(rng);
+
+    let (marker, filler) = letters.split_at(14);
+    let filler = &filler[..3];
+
+    let mut input: String = (0..size.saturating_sub(14))
+        .map(|_| *filler.choose(rng).unwrap())
+        .collect();
+    input.extend(marker);
+    input.push('\n');
+
+    input
+}
@@ -0,0 +1,122 @@
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+const TOTAL_SPACE: usize = 70_000_000;
+const SPACE_NEEDED: usize = 30_000_000;
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    files: Vec<(String, usize)>,
+}
+
+impl Dir {
+    /// A name that nothing else in here has, with an extension some of the
+    /// time for files
+    fn unused_name(&self, rng: &mut Rng, dirs: &[Dir], is_file: bool) -> String {
+        loop {
+            let len = rng.gen_range(1..=8);
+            let mut name: String = (0..len).map(|_| rng.gen_range('a'..='z')).collect();
+
+            if is_file && rng.gen_bool(0.5) {
+                let extension = ["txt", "dat", "lst", "log"].choose(rng).unwrap();
+                write!(name, ".{extension}").unwrap();
+            }
+
+            let taken = self.files.iter().any(|(other, _)| *other == name)
+                || self.dirs.iter().any(|i| dirs[*i].name == name);
+
+            if !taken {
+                return name;
+            }
+        }
+    }
+}
+
+/// A terminal session exploring a filesystem with `size` files in it
+///
+/// The disk always ends up full enough that something has to be deleted to
+/// make room for the update, and there is always a directory big enough to
+/// free it up.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut dirs = vec![Dir {
+        name: "/".to_string(),
+        ..Dir::default()
+    }];
+
+    for i in 1..=size / 4 + 1 {
+        let parent = rng.gen_range(0..i);
+        let name = dirs[parent].unused_name(rng, &dirs, false);
+
+        dirs[parent].dirs.push(i);
+        dirs.push(Dir {
+            name,
+            ..Dir::default()
+        });
+    }
+
+    let mut sizes: Vec<usize> = (0..size).map(|_| rng.gen_range(1..=300_000)).collect();
+
+    // Lots of files would overflow the disk, so shrink them to leave room for
+    // the big file below
+    let used: usize = sizes.iter().sum();
+    if used > SPACE_NEEDED {
+        for size in &mut sizes {
+            *size = (*size * SPACE_NEEDED / used).max(1);
+        }
+    }
+
+    // One big file fills the disk up to between 50M and 60M, leaving 10M to
+    // 20M to free up. The big file is more than that on its own, so the
+    // directory it goes in can always be deleted to make room
+    let used: usize = sizes.iter().sum();
+    let target = rng.gen_range(TOTAL_SPACE - 20_000_000..=TOTAL_SPACE - 10_000_000);
+    let big_file_dir = rng.gen_range(1..dirs.len());
+    let name = dirs[big_file_dir].unused_name(rng, &dirs, true);
+    dirs[big_file_dir].files.push((name, target - used));
+
+    for size in sizes {
+        let dir = rng.gen_range(0..dirs.len());
+        let name = dirs[dir].unused_name(rng, &dirs, true);
+
+        dirs[dir].files.push((name, size));
+    }
+
+    let mut input = String::new();
+    writeln!(input, "$ cd /").unwrap();
+    explore(rng, &dirs, 0, &mut input);
+
+    input
+}
+
+/// Lists `dir` and then walks into each directory inside it
+fn explore(rng: &mut Rng, dirs: &[Dir], dir: usize, input: &mut String) {
+    let dir = &dirs[dir];
+
+    let mut listing: Vec<String> = dir
+        .dirs
+        .iter()
+        .map(|child| format!("dir {}", dirs[*child].name))
+        .chain(
+            dir.files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        )
+        .collect();
+    listing.shuffle(rng);
+
+    writeln!(input, "$ ls").unwrap();
+    for line in listing {
+        writeln!(input, "{line}").unwrap();
+    }
+
+    for child in &dir.dirs {
+        writeln!(input, "$ cd {}", dirs[*child].name).unwrap();
+        explore(rng, dirs, *child, input);
+        writeln!(input, "$ cd ..").unwrap();
+    }
+}
@@ -0,0 +1,16 @@
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// A square forest `size` trees across
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut input = String::with_capacity(size * (size + 1));
+
+    for _ in 0..size {
+        input.extend((0..size).map(|_| rng.gen_range('0'..='9')));
+        input.push('\n');
+    }
+
+    input
+}
@@ -0,0 +1,17 @@
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+/// A rope dragged around for `size` moves
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let dir = ['U', 'D', 'L', 'R'].choose(rng).unwrap();
+        writeln!(input, "{dir} {}", rng.gen_range(1..=20)).unwrap();
+    }
+
+    input
+}
@@ -0,0 +1,39 @@
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// The CPU only ever runs for 240 cycles, so every program is at least that
+/// long, and `size` instructions if that is longer still
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let mut x: isize = 1;
+    let mut cycles = 0;
+    let mut instructions = 0;
+
+    while cycles < 240 || instructions < size {
+        if rng.gen_bool(0.3) {
+            writeln!(input, "noop").unwrap();
+            cycles += 1;
+        } else {
+            // Keep the sprite on the screen most of the time, so something
+            // gets drawn
+            let amount = loop {
+                let amount = rng.gen_range(-20..=20);
+
+                if amount != 0 && (-1..=40).contains(&(x + amount)) {
+                    break amount;
+                }
+            };
+
+            writeln!(input, "addx {amount}").unwrap();
+            x += amount;
+            cycles += 2;
+        }
+
+        instructions += 1;
+    }
+
+    input
+}
@@ -0,0 +1,56 @@
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+const PRIMES: [u64; 24] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89,
+];
+
+/// `size` monkeys, with at least two so every monkey has someone else to
+/// throw to
+///
+/// Part 1 works on the real worry levels, which only stay small because each
+/// one gets divided by 3 after every inspection. Items can pass through
+/// dozens of monkeys in 20 rounds, so monkeys only ever multiply by 2 or 3 to
+/// keep them from overflowing.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let monkeys = size.max(2);
+    let mut input = String::new();
+
+    for i in 0..monkeys {
+        if i > 0 {
+            writeln!(input).unwrap();
+        }
+
+        let items: Vec<String> = (0..rng.gen_range(0..=6))
+            .map(|_| rng.gen_range(1..100).to_string())
+            .collect();
+
+        let operation = if rng.gen_bool(0.5) {
+            format!("old + {}", rng.gen_range(1..=8))
+        } else {
+            format!("old * {}", rng.gen_range(2..=3))
+        };
+
+        let mut targets: Vec<usize> = (0..monkeys).filter(|target| *target != i).collect();
+        targets.shuffle(rng);
+        let if_true = targets[0];
+        let if_false = *targets.last().unwrap();
+
+        writeln!(input, "Monkey {i}:").unwrap();
+        writeln!(input, "  Starting items: {}", items.join(", ")).unwrap();
+        writeln!(input, "  Operation: new = {operation}").unwrap();
+        writeln!(
+            input,
+            "  Test: divisible by {}",
+            PRIMES.choose(rng).unwrap()
+        )
+        .unwrap();
+        writeln!(input, "    If true: throw to monkey {if_true}").unwrap();
+        writeln!(input, "    If false: throw to monkey {if_false}").unwrap();
+    }
+
+    input
+}
@@ -0,0 +1,50 @@
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+/// A heightmap `size` squares wide
+///
+/// The heights are random, so on their own there is rarely a way up. We carve
+/// a staircase from `S` in the top left to `E` in the bottom right, going
+/// right and down and climbing at most one step at a time, so there is always
+/// at least one path.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(30);
+    let height = (width / 4).max(2);
+
+    let mut rows: Vec<Vec<u8>> = (0..height)
+        .map(|_| (0..width).map(|_| rng.gen_range(b'a'..=b'z')).collect())
+        .collect();
+
+    // Every step right or down along the staircase, and which of them climb
+    let mut steps = vec![(1, 0); width - 1];
+    steps.extend(vec![(0, 1); height - 1]);
+    steps.shuffle(rng);
+
+    let mut climbs = vec![true; 25];
+    climbs.resize(steps.len(), false);
+    climbs.shuffle(rng);
+
+    let (mut x, mut y) = (0, 0);
+    let mut elevation = b'a';
+    rows[y][x] = b'S';
+
+    for ((dx, dy), climb) in steps.into_iter().zip(climbs) {
+        x += dx;
+        y += dy;
+
+        if climb {
+            elevation += 1;
+        }
+        rows[y][x] = elevation;
+    }
+    rows[y][x] = b'E';
+
+    let mut input = String::with_capacity((width + 1) * height);
+    for row in rows {
+        input.extend(row.into_iter().map(char::from));
+        input.push('\n');
+    }
+
+    input
+}
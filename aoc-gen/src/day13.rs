@@ -0,0 +1,42 @@
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// `size` pairs of packets
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for i in 0..size {
+        if i > 0 {
+            writeln!(input).unwrap();
+        }
+
+        for _ in 0..2 {
+            packet(rng, 0, &mut input);
+            writeln!(input).unwrap();
+        }
+    }
+
+    input
+}
+
+/// A list of up to 5 items, nested up to 4 deep
+fn packet(rng: &mut Rng, depth: usize, input: &mut String) {
+    input.push('[');
+
+    for i in 0..rng.gen_range(0..=5) {
+        if i > 0 {
+            input.push(',');
+        }
+
+        if depth < 4 && rng.gen_bool(0.3) {
+            packet(rng, depth + 1, input);
+        } else {
+            write!(input, "{}", rng.gen_range(0..=10)).unwrap();
+        }
+    }
+
+    input.push(']');
+}
@@ -0,0 +1,45 @@
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// A cave with `size` paths of rock in it
+///
+/// Sand pours in at x=500, and piles up into a triangle as wide as it is
+/// tall, so the rocks spread out from there as the cave gets deeper.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let depth = 20 + size.min(150) as isize;
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let y = rng.gen_range(1..=depth);
+        let x = rng.gen_range(500 - y..=500 + y);
+        let mut points = vec![(x, y)];
+
+        for i in 0..rng.gen_range(1..=4) {
+            let (x, y) = *points.last().unwrap();
+            let length = rng.gen_range(1..=8);
+
+            // Alternate between horizontal and vertical lines, so no two
+            // lines in a row head the same way
+            let point = if i % 2 == 0 {
+                (x + length * if rng.gen_bool(0.5) { 1 } else { -1 }, y)
+            } else {
+                (
+                    x,
+                    (y + length * if rng.gen_bool(0.5) { 1 } else { -1 }).max(1),
+                )
+            };
+
+            if point != (x, y) {
+                points.push(point);
+            }
+        }
+
+        let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        writeln!(input, "{}", points.join(" -> ")).unwrap();
+    }
+
+    input
+}
@@ -0,0 +1,47 @@
+use std::fmt::Write;
+
+use rand::Rng as _;
+
+use crate::Rng;
+
+const MAX: i64 = 4_000_000;
+
+/// `size` sensors spread over the area the distress beacon can be in
+///
+/// We pick somewhere for the distress beacon first, and every sensor's
+/// closest beacon is nearer to it than that, so no sensor can rule it out and
+/// there is always somewhere left for it to be.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let hidden = (rng.gen_range(0..=MAX), rng.gen_range(0..=MAX));
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let sensor = loop {
+            let sensor = (rng.gen_range(0..=MAX), rng.gen_range(0..=MAX));
+
+            if sensor != hidden {
+                break sensor;
+            }
+        };
+
+        let distance_to_hidden = distance(sensor, hidden);
+        let distance = rng.gen_range(0..distance_to_hidden).min(MAX / 4);
+
+        let dx = rng.gen_range(-distance..=distance);
+        let dy = (distance - dx.abs()) * if rng.gen_bool(0.5) { 1 } else { -1 };
+        let beacon = (sensor.0 + dx, sensor.1 + dy);
+
+        writeln!(
+            input,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sensor.0, sensor.1, beacon.0, beacon.1
+        )
+        .unwrap();
+    }
+
+    input
+}
+
+fn distance(a: (i64, i64), b: (i64, i64)) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
@@ -0,0 +1,87 @@
+//! Random puzzle inputs for every day, for stress testing the solutions on
+//! inputs much bigger than the real ones, fuzzing the parsers, and building
+//! benchmark fixtures
+//!
+//! Every generator is seeded, so the same day, size and seed always give back
+//! the same input. What `size` counts depends on the day, but it is always the
+//! thing the real inputs have lots of, like elves or monkeys or moves. Inputs
+//! are built so the solutions can always find an answer, not just so they
+//! parse.
+
+use rand::SeedableRng;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+
+/// The generators all share one RNG so that a seed means the same thing on
+/// every platform and for every version of `rand`
+type Rng = rand_chacha::ChaCha8Rng;
+
+/// Makes a random input for `day`, or `None` if we don't have a generator
+/// for that day
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    let generate = match day {
+        1 => day01::generate,
+        2 => day02::generate,
+        3 => day03::generate,
+        4 => day04::generate,
+        5 => day05::generate,
+        6 => day06::generate,
+        7 => day07::generate,
+        8 => day08::generate,
+        9 => day09::generate,
+        10 => day10::generate,
+        11 => day11::generate,
+        12 => day12::generate,
+        13 => day13::generate,
+        14 => day14::generate,
+        15 => day15::generate,
+        _ => return None,
+    };
+
+    let mut rng = Rng::seed_from_u64(seed);
+
+    Some(generate(&mut rng, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_gives_the_same_input() {
+        for day in 1..=15 {
+            assert_eq!(generate(day, 20, 7), generate(day, 20, 7), "Day {day}");
+            assert_ne!(generate(day, 20, 7), generate(day, 20, 8), "Day {day}");
+        }
+    }
+
+    #[test]
+    fn bigger_sizes_give_bigger_inputs() {
+        for day in 1..=15 {
+            let small = generate(day, 10, 0).unwrap();
+            let big = generate(day, 1000, 0).unwrap();
+
+            assert!(big.len() > small.len(), "Day {day}");
+        }
+    }
+
+    #[test]
+    fn unknown_days() {
+        assert_eq!(generate(0, 10, 0), None);
+        assert_eq!(generate(26, 10, 0), None);
+    }
+}
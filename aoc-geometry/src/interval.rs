@@ -0,0 +1,93 @@
+/// Every whole number from `start` to `end`, both ends included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    start: i64,
+    end: i64,
+}
+
+impl Interval {
+    /// `None` when `end` comes before `start`
+    pub const fn new(start: i64, end: i64) -> Option<Self> {
+        if start <= end {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+
+    /// The interval holding just `at`
+    pub const fn point(at: i64) -> Self {
+        Self { start: at, end: at }
+    }
+
+    pub const fn start(self) -> i64 {
+        self.start
+    }
+
+    pub const fn end(self) -> i64 {
+        self.end
+    }
+
+    /// How many whole numbers are in it, which is never 0. Saturates for the
+    /// one interval holding every `i64`
+    pub const fn size(self) -> u64 {
+        self.end.abs_diff(self.start).saturating_add(1)
+    }
+
+    pub const fn contains(self, at: i64) -> bool {
+        self.start <= at && at <= self.end
+    }
+
+    /// Whether all of `other` is inside this one
+    pub const fn covers(self, other: Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether there's at least one number in both
+    pub const fn overlaps(self, other: Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(self, other: Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Whether the two could be joined into one interval without taking in
+    /// any numbers that aren't in either of them
+    pub(crate) const fn touches(self, other: Interval) -> bool {
+        self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: i64, end: i64) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn ends_are_included() {
+        let sections = interval(2, 4);
+
+        assert_eq!(sections.size(), 3);
+        assert!(sections.contains(2) && sections.contains(4));
+        assert!(!sections.contains(5));
+        assert_eq!(Interval::new(4, 2), None);
+        assert_eq!(Interval::point(7).size(), 1);
+    }
+
+    #[test]
+    fn overlaps_on_a_shared_end() {
+        assert!(interval(5, 7).overlaps(interval(7, 9)));
+        assert!(!interval(2, 4).overlaps(interval(6, 8)));
+        assert!(interval(2, 8).covers(interval(3, 7)));
+        assert!(!interval(3, 7).covers(interval(2, 8)));
+        assert_eq!(
+            interval(2, 6).intersection(interval(4, 8)),
+            Some(interval(4, 6))
+        );
+        assert_eq!(interval(2, 4).intersection(interval(6, 8)), None);
+    }
+}
@@ -0,0 +1,16 @@
+//! Ranges of whole numbers, and shapes measured in Manhattan distance
+//!
+//! [`Interval`] is an inclusive range like the sections in `2-4`, and
+//! [`IntervalSet`] is any number of them at once, kept merged so it can be
+//! measured and combined with other sets. [`ManhattanBall`] is every point
+//! within some number of steps of a centre, which is a diamond on the plane.
+//! Turned 45 degrees, a diamond becomes an upright square, so
+//! [`RotatedRect`] works in those turned coordinates instead.
+
+mod interval;
+mod manhattan;
+mod set;
+
+pub use interval::Interval;
+pub use manhattan::{ManhattanBall, Point, RotatedRect};
+pub use set::IntervalSet;
@@ -0,0 +1,161 @@
+use crate::Interval;
+
+/// A point on a plane with no edges, with `y` growing downwards like it does
+/// in the grids
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Steps needed to get to `other` moving only along the axes
+    pub const fn manhattan_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Where this point ends up in the coordinates [`RotatedRect`] uses,
+    /// `(x + y, x - y)`
+    pub const fn rotated(self) -> (i64, i64) {
+        (self.x + self.y, self.x - self.y)
+    }
+}
+
+/// Every point within `radius` steps of `centre`, which makes a diamond
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ManhattanBall {
+    pub centre: Point,
+    pub radius: u64,
+}
+
+impl ManhattanBall {
+    pub const fn new(centre: Point, radius: u64) -> Self {
+        Self { centre, radius }
+    }
+
+    /// The smallest ball around `centre` with `edge` in it
+    pub const fn reaching(centre: Point, edge: Point) -> Self {
+        Self::new(centre, centre.manhattan_distance(edge))
+    }
+
+    pub const fn contains(self, p: Point) -> bool {
+        self.centre.manhattan_distance(p) <= self.radius
+    }
+
+    /// The `x`s of the points in row `y` that are inside the ball
+    pub fn row(self, y: i64) -> Option<Interval> {
+        let reach = self.radius.checked_sub(self.centre.y.abs_diff(y))? as i64;
+
+        Interval::new(self.centre.x - reach, self.centre.x + reach)
+    }
+
+    /// The ball turned 45 degrees, where it becomes an upright square
+    pub fn rotated(self) -> RotatedRect {
+        let (u, v) = self.centre.rotated();
+        let radius = self.radius as i64;
+
+        RotatedRect {
+            u: Interval::new(u - radius, u + radius).expect("The radius isn't negative"),
+            v: Interval::new(v - radius, v + radius).expect("The radius isn't negative"),
+        }
+    }
+}
+
+/// An upright rectangle in coordinates turned 45 degrees, with `u` running
+/// along `x + y` and `v` along `x - y`. Diamonds on the plane are rectangles
+/// here, so finding where they overlap is just a matter of intervals
+///
+/// Only the `(u, v)` where `u` and `v` are both odd or both even come from a
+/// whole [`Point`], so a rectangle holds some spots that no point lands on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RotatedRect {
+    pub u: Interval,
+    pub v: Interval,
+}
+
+impl RotatedRect {
+    pub fn contains(self, p: Point) -> bool {
+        let (u, v) = p.rotated();
+
+        self.u.contains(u) && self.v.contains(v)
+    }
+
+    pub fn intersection(self, other: RotatedRect) -> Option<RotatedRect> {
+        Some(RotatedRect {
+            u: self.u.intersection(other.u)?,
+            v: self.v.intersection(other.v)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn balls_are_diamonds() {
+        let sensor = ManhattanBall::reaching(Point::new(8, 7), Point::new(2, 10));
+
+        assert_eq!(sensor.radius, 9);
+        assert!(sensor.contains(Point::new(8, -2)));
+        assert!(!sensor.contains(Point::new(9, -2)));
+        assert_eq!(sensor.row(10), Interval::new(2, 14));
+        assert_eq!(sensor.row(-2), Some(Interval::point(8)));
+        assert_eq!(sensor.row(-3), None);
+    }
+
+    #[test]
+    fn overlapping_balls_overlap_when_rotated() {
+        let a = ManhattanBall::new(Point::new(0, 0), 2).rotated();
+        let b = ManhattanBall::new(Point::new(4, 0), 2).rotated();
+        let c = ManhattanBall::new(Point::new(5, 0), 2).rotated();
+
+        let shared = a.intersection(b).unwrap();
+        assert!(shared.contains(Point::new(2, 0)));
+        assert!(!shared.contains(Point::new(1, 0)));
+        assert_eq!(a.intersection(c), None);
+    }
+
+    fn point() -> impl Strategy<Value = Point> {
+        (-30..30i64, -30..30i64).prop_map(|(x, y)| Point::new(x, y))
+    }
+
+    proptest! {
+        #[test]
+        fn rotating_keeps_the_same_points(centre in point(), radius in 0..20u64, p in point()) {
+            let ball = ManhattanBall::new(centre, radius);
+
+            prop_assert_eq!(ball.contains(p), ball.rotated().contains(p));
+        }
+
+        #[test]
+        fn rows_hold_the_points_in_the_ball(centre in point(), radius in 0..20u64, p in point()) {
+            let ball = ManhattanBall::new(centre, radius);
+            let in_row = ball.row(p.y).is_some_and(|row| row.contains(p.x));
+
+            prop_assert_eq!(ball.contains(p), in_row);
+        }
+
+        #[test]
+        fn rotated_overlaps_hold_the_shared_points(
+            a in point(),
+            b in point(),
+            radii in (0..20u64, 0..20u64),
+            p in point(),
+        ) {
+            let (a, b) = (ManhattanBall::new(a, radii.0), ManhattanBall::new(b, radii.1));
+            let shared = a.rotated().intersection(b.rotated());
+
+            prop_assert_eq!(
+                a.contains(p) && b.contains(p),
+                shared.is_some_and(|shared| shared.contains(p))
+            );
+        }
+    }
+}
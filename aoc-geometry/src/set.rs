@@ -0,0 +1,262 @@
+use crate::Interval;
+
+/// Any collection of whole numbers, stored as the fewest intervals that
+/// cover them. They're kept in order, and no two of them overlap or sit
+/// right next to each other
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every number in `interval`, joining it up with any intervals it
+    /// overlaps or touches
+    pub fn insert(&mut self, interval: Interval) {
+        let first = self
+            .intervals
+            .partition_point(|i| !i.touches(interval) && i.end() < interval.start());
+        let last = self
+            .intervals
+            .partition_point(|i| i.touches(interval) || i.start() < interval.start());
+
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, i| {
+                Interval::new(merged.start().min(i.start()), merged.end().max(i.end()))
+                    .expect("Both ends come from intervals")
+            });
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let intervals = self
+            .intervals
+            .iter()
+            .flat_map(|a| other.overlapping(*a).filter_map(|b| a.intersection(b)))
+            .collect();
+
+        Self { intervals }
+    }
+
+    /// Everything in this set that isn't in `other`
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+
+        for a in &self.intervals {
+            let mut start = Some(a.start());
+
+            for b in other.overlapping(*a) {
+                let Some(from) = start else { break };
+
+                if from < b.start() {
+                    intervals.extend(Interval::new(from, b.start() - 1));
+                }
+                start = b.end().checked_add(1).filter(|next| *next <= a.end());
+            }
+
+            if let Some(from) = start {
+                intervals.extend(Interval::new(from, a.end()));
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// How many whole numbers are in the set
+    pub fn measure(&self) -> u64 {
+        self.intervals
+            .iter()
+            .fold(0, |total: u64, i| total.saturating_add(i.size()))
+    }
+
+    pub fn contains(&self, at: i64) -> bool {
+        let i = self.intervals.partition_point(|i| i.end() < at);
+
+        self.intervals.get(i).is_some_and(|i| i.contains(at))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The intervals making up the set, from lowest to highest
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    /// The intervals in this set that share a number with `interval`
+    fn overlapping(&self, interval: Interval) -> impl Iterator<Item = Interval> + '_ {
+        let first = self
+            .intervals
+            .partition_point(|i| i.end() < interval.start());
+
+        self.intervals[first..]
+            .iter()
+            .copied()
+            .take_while(move |i| i.start() <= interval.end())
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        Self {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut sorted: Vec<_> = iter.into_iter().collect();
+        sorted.sort_unstable();
+
+        let mut intervals: Vec<Interval> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if last.touches(interval) => {
+                    *last = Interval::new(last.start(), last.end().max(interval.end()))
+                        .expect("Sorted intervals only grow");
+                }
+                _ => intervals.push(interval),
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn interval(start: i64, end: i64) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn joins_intervals_that_touch() {
+        let set: IntervalSet = [interval(5, 7), interval(1, 2), interval(3, 3)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [interval(1, 3), interval(5, 7)]
+        );
+        assert_eq!(set.measure(), 6);
+        assert!(set.contains(3) && !set.contains(4));
+    }
+
+    #[test]
+    fn subtracting_splits_intervals() {
+        let row = IntervalSet::from(interval(0, 20));
+        let seen: IntervalSet = [interval(-2, 13), interval(15, 25)].into_iter().collect();
+
+        assert_eq!(
+            row.difference(&seen).iter().collect::<Vec<_>>(),
+            [interval(14, 14)]
+        );
+    }
+
+    #[test]
+    fn copes_with_the_ends_of_i64() {
+        let everything = IntervalSet::from(interval(i64::MIN, i64::MAX));
+        let top = IntervalSet::from(Interval::point(i64::MAX));
+
+        assert_eq!(everything.measure(), u64::MAX);
+        assert_eq!(
+            everything.difference(&top).iter().collect::<Vec<_>>(),
+            [interval(i64::MIN, i64::MAX - 1)]
+        );
+    }
+
+    /// Short intervals close together, so sets often overlap and touch
+    fn interval_set() -> impl Strategy<Value = Vec<Interval>> {
+        let interval = (-20..20i64, 0..8i64).prop_map(|(start, len)| interval(start, start + len));
+
+        prop::collection::vec(interval, 0..6)
+    }
+
+    /// The same set written out one number at a time
+    fn numbers(intervals: &[Interval]) -> BTreeSet<i64> {
+        intervals.iter().flat_map(|i| i.start()..=i.end()).collect()
+    }
+
+    fn numbers_in(set: &IntervalSet) -> BTreeSet<i64> {
+        numbers(&set.iter().collect::<Vec<_>>())
+    }
+
+    fn is_tidy(set: &IntervalSet) -> bool {
+        set.intervals
+            .windows(2)
+            .all(|pair| pair[0].end().saturating_add(1) < pair[1].start())
+    }
+
+    proptest! {
+        #[test]
+        fn collecting_matches_the_numbers(a in interval_set()) {
+            let set: IntervalSet = a.iter().copied().collect();
+
+            prop_assert!(is_tidy(&set));
+            prop_assert_eq!(numbers_in(&set), numbers(&a));
+            prop_assert_eq!(set.measure(), numbers(&a).len() as u64);
+            for n in -30..30 {
+                prop_assert_eq!(set.contains(n), numbers(&a).contains(&n));
+            }
+        }
+
+        #[test]
+        fn inserting_matches_collecting(a in interval_set()) {
+            let mut inserted = IntervalSet::new();
+            for interval in &a {
+                inserted.insert(*interval);
+            }
+
+            prop_assert_eq!(inserted, a.into_iter().collect::<IntervalSet>());
+        }
+
+        #[test]
+        fn set_algebra_matches_the_numbers(a in interval_set(), b in interval_set()) {
+            let (set_a, set_b): (IntervalSet, IntervalSet) =
+                (a.iter().copied().collect(), b.iter().copied().collect());
+            let (numbers_a, numbers_b) = (numbers(&a), numbers(&b));
+
+            let union = set_a.union(&set_b);
+            let intersection = set_a.intersection(&set_b);
+            let difference = set_a.difference(&set_b);
+
+            prop_assert!(is_tidy(&union) && is_tidy(&intersection) && is_tidy(&difference));
+            prop_assert_eq!(numbers_in(&union), &numbers_a | &numbers_b);
+            prop_assert_eq!(numbers_in(&intersection), &numbers_a & &numbers_b);
+            prop_assert_eq!(numbers_in(&difference), &numbers_a - &numbers_b);
+        }
+
+        #[test]
+        fn set_algebra_identities(a in interval_set(), b in interval_set()) {
+            let (a, b): (IntervalSet, IntervalSet) =
+                (a.into_iter().collect(), b.into_iter().collect());
+
+            prop_assert_eq!(a.union(&b), b.union(&a));
+            prop_assert_eq!(a.intersection(&b), b.intersection(&a));
+            prop_assert_eq!(a.difference(&b).union(&a.intersection(&b)), a.clone());
+            prop_assert!(a.difference(&b).intersection(&b).is_empty());
+            prop_assert_eq!(
+                a.union(&b).measure() + a.intersection(&b).measure(),
+                a.measure() + b.measure()
+            );
+        }
+    }
+}
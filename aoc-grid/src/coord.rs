@@ -0,0 +1,195 @@
+use std::ops::{Add, AddAssign, Sub};
+
+/// A position in a grid, with `y` growing downwards
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Coord {
+    pub const ORIGIN: Coord = Coord::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// The coord one step away in `dir`
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.offset()
+    }
+
+    /// The four coords that share an edge with this one
+    pub fn neighbours_4(self) -> impl Iterator<Item = Coord> {
+        Direction::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// The eight coords that share an edge or a corner with this one
+    pub fn neighbours_8(self) -> impl Iterator<Item = Coord> {
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Coord::new(x, y)))
+            .filter(|offset| *offset != Coord::ORIGIN)
+            .map(move |offset| self + offset)
+    }
+
+    /// Steps needed to get to `other` moving only along the axes
+    pub fn manhattan_distance(self, other: Coord) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Steps needed to get to `other` when diagonal steps are allowed too
+    pub fn chebyshev_distance(self, other: Coord) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Shrinks each axis down to -1, 0 or 1, which turns a difference between
+    /// two coords into a single step towards the first
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Which way `other` is from here, if it is one step away along an axis
+    pub fn direction_to(self, other: Coord) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|dir| self.step(*dir) == other)
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Coord) -> Coord {
+        Coord::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Coord {
+    fn add_assign(&mut self, rhs: Coord) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Coord) -> Coord {
+        Coord::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn offset(self) -> Coord {
+        match self {
+            Direction::Up => Coord::new(0, -1),
+            Direction::Down => Coord::new(0, 1),
+            Direction::Left => Coord::new(-1, 0),
+            Direction::Right => Coord::new(1, 0),
+        }
+    }
+
+    /// An arrow pointing this way, handy for drawing paths
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+/// The smallest rectangle containing a set of coords, edges included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Coord,
+    pub max: Coord,
+}
+
+impl Bounds {
+    /// Bounds that only contain `c`
+    pub fn around(c: Coord) -> Self {
+        Self { min: c, max: c }
+    }
+
+    /// Grows the bounds just enough to take in `c`
+    pub fn extend(&mut self, c: Coord) {
+        self.min = Coord::new(self.min.x.min(c.x), self.min.y.min(c.y));
+        self.max = Coord::new(self.max.x.max(c.x), self.max.y.max(c.y));
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        (self.min.x..=self.max.x).contains(&c.x) && (self.min.y..=self.max.y).contains(&c.y)
+    }
+
+    pub fn width(&self) -> usize {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    /// Every coord inside the bounds, a row at a time
+    pub fn coords(self) -> impl Iterator<Item = Coord> {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Coord::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn up_is_towards_the_first_line() {
+        assert_eq!(Coord::new(3, 3).step(Direction::Up), Coord::new(3, 2));
+        assert_eq!(Coord::new(3, 3).step(Direction::Right), Coord::new(4, 3));
+    }
+
+    #[test]
+    fn neighbours() {
+        let c = Coord::new(1, 1);
+
+        assert_eq!(c.neighbours_4().count(), 4);
+        assert_eq!(c.neighbours_8().count(), 8);
+        assert!(c.neighbours_8().all(|n| c.chebyshev_distance(n) == 1));
+        assert!(!c.neighbours_8().any(|n| n == c));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Coord::new(0, 0);
+        let b = Coord::new(3, -4);
+
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!((b - a).signum(), Coord::new(1, -1));
+    }
+
+    #[test]
+    fn bounds_grow_to_fit() {
+        let mut bounds = Bounds::around(Coord::new(2, 2));
+        bounds.extend(Coord::new(0, 3));
+
+        assert_eq!(bounds.min, Coord::new(0, 2));
+        assert_eq!(bounds.max, Coord::new(2, 3));
+        assert_eq!((bounds.width(), bounds.height()), (3, 2));
+        assert_eq!(bounds.coords().count(), 6);
+    }
+}
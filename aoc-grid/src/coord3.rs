@@ -0,0 +1,125 @@
+use std::ops::{Add, AddAssign, Sub};
+
+/// A position in space, for worlds made of cubes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Coord3 {
+    pub const ORIGIN: Coord3 = Coord3::new(0, 0, 0);
+
+    /// One step along each axis, both ways
+    pub const OFFSETS: [Coord3; 6] = [
+        Coord3::new(1, 0, 0),
+        Coord3::new(-1, 0, 0),
+        Coord3::new(0, 1, 0),
+        Coord3::new(0, -1, 0),
+        Coord3::new(0, 0, 1),
+        Coord3::new(0, 0, -1),
+    ];
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The six coords that share a face with this one
+    pub fn neighbours_6(self) -> impl Iterator<Item = Coord3> {
+        Self::OFFSETS.into_iter().map(move |offset| self + offset)
+    }
+
+    /// Steps needed to get to `other` moving only along the axes
+    pub fn manhattan_distance(self, other: Coord3) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+impl Add for Coord3 {
+    type Output = Coord3;
+
+    fn add(self, rhs: Coord3) -> Coord3 {
+        Coord3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Coord3 {
+    fn add_assign(&mut self, rhs: Coord3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord3 {
+    type Output = Coord3;
+
+    fn sub(self, rhs: Coord3) -> Coord3 {
+        Coord3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+/// The smallest box containing a set of coords, faces included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds3 {
+    pub min: Coord3,
+    pub max: Coord3,
+}
+
+impl Bounds3 {
+    /// Bounds that only contain `c`
+    pub fn around(c: Coord3) -> Self {
+        Self { min: c, max: c }
+    }
+
+    /// Grows the bounds just enough to take in `c`
+    pub fn extend(&mut self, c: Coord3) {
+        self.min = Coord3::new(
+            self.min.x.min(c.x),
+            self.min.y.min(c.y),
+            self.min.z.min(c.z),
+        );
+        self.max = Coord3::new(
+            self.max.x.max(c.x),
+            self.max.y.max(c.y),
+            self.max.z.max(c.z),
+        );
+    }
+
+    pub fn contains(&self, c: Coord3) -> bool {
+        (self.min.x..=self.max.x).contains(&c.x)
+            && (self.min.y..=self.max.y).contains(&c.y)
+            && (self.min.z..=self.max.z).contains(&c.z)
+    }
+
+    /// How many coords are inside the bounds
+    pub fn volume(&self) -> usize {
+        let size = self.max - self.min;
+
+        (size.x as usize + 1) * (size.y as usize + 1) * (size.z as usize + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours_share_a_face() {
+        let c = Coord3::new(1, 2, 3);
+
+        assert_eq!(c.neighbours_6().count(), 6);
+        assert!(c.neighbours_6().all(|n| c.manhattan_distance(n) == 1));
+    }
+
+    #[test]
+    fn bounds_grow_to_fit() {
+        let mut bounds = Bounds3::around(Coord3::new(2, 2, 2));
+        bounds.extend(Coord3::new(0, 3, 2));
+
+        assert_eq!(bounds.min, Coord3::new(0, 2, 2));
+        assert_eq!(bounds.max, Coord3::new(2, 3, 2));
+        assert_eq!(bounds.volume(), 6);
+        assert!(bounds.contains(Coord3::new(1, 3, 2)));
+        assert!(!bounds.contains(Coord3::new(1, 3, 3)));
+    }
+}
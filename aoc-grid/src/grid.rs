@@ -0,0 +1,224 @@
+use std::ops::{Index, IndexMut};
+
+use aoc_core::Unexpected;
+
+use crate::{Bounds, Coord, Direction};
+
+/// A rectangular map with a value in every cell, stored a row at a time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a map drawn with one character per cell, like most puzzle
+    /// inputs with a map in them
+    ///
+    /// `cell` turns each character into a value, and returns `None` for
+    /// characters that don't belong in the map. Every line has to be the same
+    /// width.
+    pub fn parse<'a>(
+        input: &'a str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, Unexpected<'a>> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.lines() {
+            let before = cells.len();
+
+            for (i, c) in line.char_indices() {
+                let value =
+                    cell(c).ok_or_else(|| Unexpected::new(&line[i..i + c.len_utf8()], expected))?;
+                cells.push(value);
+            }
+
+            let line_width = cells.len() - before;
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(Unexpected::new(
+                        line,
+                        format!("a row that is {width} cells wide"),
+                    ))
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(Unexpected::new(input, "at least one row of cells")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            min: Coord::ORIGIN,
+            max: Coord::new(self.width as isize - 1, self.height as isize - 1),
+        }
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        (0..self.width as isize).contains(&c.x) && (0..self.height as isize).contains(&c.y)
+    }
+
+    fn index_of(&self, c: Coord) -> Option<usize> {
+        self.contains(c)
+            .then(|| c.y as usize * self.width + c.x as usize)
+    }
+
+    /// The value at `c`, or `None` when `c` is off the edge of the grid
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        self.index_of(c).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        self.index_of(c).map(|i| &mut self.cells[i])
+    }
+
+    /// Every coord in the grid, a row at a time
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        self.bounds().coords()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(&self.cells)
+    }
+
+    /// The first coord whose value matches, searching a row at a time
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(c, _)| c)
+    }
+
+    /// The neighbours of `c` along the axes that are inside the grid
+    pub fn neighbours_4(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        c.neighbours_4().filter(|n| self.contains(*n))
+    }
+
+    /// The neighbours of `c`, diagonals included, that are inside the grid
+    pub fn neighbours_8(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        c.neighbours_8().filter(|n| self.contains(*n))
+    }
+
+    /// Every coord from the one after `c` to the edge of the grid, heading
+    /// in `dir`
+    pub fn ray(&self, c: Coord, dir: Direction) -> impl Iterator<Item = Coord> + '_ {
+        std::iter::successors(Some(c.step(dir)), move |c| Some(c.step(dir)))
+            .take_while(|c| self.contains(*c))
+    }
+
+    /// Draws the grid a row at a time, one character per cell
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        self.cells
+            .chunks(self.width)
+            .map(|row| row.iter().map(&mut cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coord) -> &T {
+        self.get(c)
+            .unwrap_or_else(|| panic!("{c:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, c: Coord) -> &mut T {
+        self.get_mut(c)
+            .unwrap_or_else(|| panic!("{c:?} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, Unexpected<'_>> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parses_a_character_map() {
+        let grid = digits("123\n456\n").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(2, 1)], 6);
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.position(|d| *d == 5), Some(Coord::new(1, 1)));
+        assert_eq!(
+            grid.render(|d| char::from_digit(*d, 10).unwrap()),
+            "123\n456"
+        );
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_problem() {
+        let input = "123\n4x6\n";
+        let err = digits(input).unwrap_err().locate(0, input);
+
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a digit");
+
+        let input = "123\n45\n";
+        let err = digits(input).unwrap_err().locate(0, input);
+
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a row that is 3 cells wide");
+
+        assert!(digits("").is_err());
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        let grid = Grid::new(3, 3, ());
+
+        assert_eq!(grid.neighbours_4(Coord::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours_8(Coord::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours_8(Coord::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn rays_run_to_the_edge() {
+        let grid = Grid::new(4, 3, ());
+        let ray: Vec<_> = grid.ray(Coord::new(1, 1), Direction::Right).collect();
+
+        assert_eq!(ray, vec![Coord::new(2, 1), Coord::new(3, 1)]);
+        assert_eq!(grid.ray(Coord::new(1, 0), Direction::Up).count(), 0);
+    }
+}
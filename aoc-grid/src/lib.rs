@@ -0,0 +1,18 @@
+//! 2D worlds for the days that need them
+//!
+//! [`Grid`] stores every cell of a fixed size map, which suits puzzles that
+//! hand us the whole map up front. [`SparseGrid`] only stores the cells that
+//! have something in them, for worlds with no real edges.
+//!
+//! `y` grows downwards in both, the same way the lines of a puzzle input do.
+//! [`Coord3`] is there for the worlds made of cubes instead.
+
+mod coord;
+mod coord3;
+mod grid;
+mod sparse;
+
+pub use coord::{Bounds, Coord, Direction};
+pub use coord3::{Bounds3, Coord3};
+pub use grid::Grid;
+pub use sparse::SparseGrid;
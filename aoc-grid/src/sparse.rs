@@ -0,0 +1,134 @@
+use std::collections::{hash_map, HashMap};
+
+use crate::{Bounds, Coord};
+
+/// A map that only stores the cells that have something in them, so it can
+/// grow in any direction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Coord, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whatever was at `c` before
+    pub fn insert(&mut self, c: Coord, value: T) -> Option<T> {
+        self.cells.insert(c, value)
+    }
+
+    pub fn remove(&mut self, c: Coord) -> Option<T> {
+        self.cells.remove(&c)
+    }
+
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        self.cells.get(&c)
+    }
+
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        self.cells.get_mut(&c)
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        self.cells.contains_key(&c)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The filled cells, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells.iter().map(|(c, value)| (*c, value))
+    }
+
+    /// The smallest rectangle holding every filled cell, or `None` when
+    /// nothing has been filled yet
+    pub fn bounds(&self) -> Option<Bounds> {
+        let mut coords = self.cells.keys();
+        let mut bounds = Bounds::around(*coords.next()?);
+
+        for c in coords {
+            bounds.extend(*c);
+        }
+
+        Some(bounds)
+    }
+
+    /// Draws the filled part of the grid a row at a time, with `empty`
+    /// standing in for the cells that have nothing in them
+    pub fn render(&self, empty: char, mut cell: impl FnMut(&T) -> char) -> String {
+        let Some(bounds) = self.bounds() else {
+            return String::new();
+        };
+
+        (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| self.get(Coord::new(x, y)).map_or(empty, &mut cell))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> FromIterator<(Coord, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coord, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(Coord, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Coord, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter)
+    }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+    type Item = (Coord, T);
+    type IntoIter = hash_map::IntoIter<Coord, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_cover_every_filled_cell() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Coord::new(-2, 5), '#');
+        grid.insert(Coord::new(1, 3), 'o');
+
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: Coord::new(-2, 3),
+                max: Coord::new(1, 5)
+            })
+        );
+        assert_eq!(grid.render('.', |c| *c), "...o\n....\n#...");
+    }
+}
@@ -0,0 +1,155 @@
+//! Small parsers that every day's input is built out of
+//!
+//! A parser is any function that takes the input it's pointed at and hands
+//! back what it found along with the input it didn't need, or an
+//! [`Unexpected`] when the input isn't what it wanted. Because `Unexpected`
+//! holds the exact slice of the input that was wrong, every error keeps its
+//! span, and [`Unexpected::locate`] can say which line and column it was on.
+//!
+//! Parsers are plain functions, so they're put together with `?` as often as
+//! with the combinators here:
+//!
+//! ```
+//! use aoc_parse::{tag, unsigned, Parsed};
+//!
+//! fn range(input: &str) -> Parsed<'_, (u64, u64)> {
+//!     let (from, input) = unsigned(input)?;
+//!     let (_, input) = tag("-")(input)?;
+//!     let (to, input) = unsigned(input)?;
+//!
+//!     Ok(((from, to), input))
+//! }
+//!
+//! assert_eq!(range("2-4,6-8"), Ok(((2, 4), ",6-8")));
+//! ```
+
+use aoc_core::Unexpected;
+
+mod numbers;
+mod structure;
+mod text;
+
+pub use numbers::{signed, unsigned};
+pub use structure::{blocks, grid, lines, separated, template};
+pub use text::{chars, one_of, tag, until, word};
+
+/// What a parser found, and the input left after it
+pub type Parsed<'a, T> = Result<(T, &'a str), Unexpected<'a>>;
+
+/// Something a parser found, along with the slice of input it came from, so
+/// checks made after parsing can still point at the right place
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spanned<'a, T> {
+    pub value: T,
+    pub span: &'a str,
+}
+
+/// Runs `parser` over the whole of `input`. Only line breaks may be left
+/// over at the end
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+) -> Result<T, Unexpected<'a>> {
+    let (value, rest) = parser(input)?;
+    let rest = rest.trim_start_matches(['\r', '\n']);
+
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(Unexpected::new(rest, "the end of the input"))
+    }
+}
+
+/// Runs `parser` and insists it uses up all of its input
+pub fn complete<'a, T>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+) -> impl Fn(&'a str) -> Parsed<'a, T> {
+    move |input| {
+        let (value, rest) = parser(input)?;
+
+        if rest.is_empty() {
+            Ok((value, rest))
+        } else {
+            Err(Unexpected::new(rest, "nothing more"))
+        }
+    }
+}
+
+/// Keeps the slice of input that `parser` used alongside what it found
+pub fn spanned<'a, T>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+) -> impl Fn(&'a str) -> Parsed<'a, Spanned<'a, T>> {
+    move |input| {
+        let (value, rest) = parser(input)?;
+        let span = &input[..input.len() - rest.len()];
+
+        Ok((Spanned { value, span }, rest))
+    }
+}
+
+/// Describes what `parser` expects in its own words, for when the primitive
+/// it's made of would say something less helpful
+pub fn label<'a, T>(
+    parser: impl Fn(&'a str) -> Parsed<'a, T>,
+    expected: &'static str,
+) -> impl Fn(&'a str) -> Parsed<'a, T> {
+    move |input| parser(input).map_err(|e| Unexpected::new(e.text, expected))
+}
+
+/// The slice to point at when a token isn't what we wanted: the run of
+/// non-whitespace at the start of `input`, a single whitespace character,
+/// or nothing at the end of a line
+fn token_at(input: &str) -> &str {
+    match input.chars().next() {
+        None | Some('\n' | '\r') => &input[..0],
+        Some(c) if c.is_whitespace() => &input[..c.len_utf8()],
+        Some(_) => {
+            let end = input.find(char::is_whitespace).unwrap_or(input.len());
+            &input[..end]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_everything_but_trailing_line_breaks() {
+        assert_eq!(parse_all("12\n\n", unsigned::<u8>), Ok(12));
+
+        let err = parse_all("12 3\n", unsigned::<u8>).unwrap_err();
+        assert_eq!(
+            (err.text, err.expected.as_str()),
+            (" 3\n", "the end of the input")
+        );
+    }
+
+    #[test]
+    fn spans_cover_what_was_used() {
+        let input = "10,20";
+        let (number, rest) = spanned(unsigned::<u8>)(input).unwrap();
+
+        assert_eq!(number.value, 10);
+        assert_eq!(number.span, "10");
+        assert_eq!(rest, ",20");
+
+        let err = complete(unsigned::<u8>)(input).unwrap_err();
+        assert_eq!(err.text, ",20");
+    }
+
+    #[test]
+    fn labels_replace_what_was_expected() {
+        let err = label(unsigned::<u8>, "an amount to add")("x").unwrap_err();
+
+        assert_eq!((err.text, err.expected.as_str()), ("x", "an amount to add"));
+    }
+
+    #[test]
+    fn tokens_stop_at_whitespace() {
+        assert_eq!(token_at("lots of text"), "lots");
+        assert_eq!(token_at(" x"), " ");
+        assert_eq!(token_at("\nx"), "");
+        assert_eq!(token_at(""), "");
+    }
+}
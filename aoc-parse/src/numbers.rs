@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use aoc_core::Unexpected;
+
+use crate::{token_at, Parsed};
+
+/// A whole number without a sign, like `42`
+pub fn unsigned<T: FromStr>(input: &str) -> Parsed<'_, T> {
+    digits(input, 0)
+}
+
+/// A whole number that may start with `-` or `+`, like `-42`
+pub fn signed<T: FromStr>(input: &str) -> Parsed<'_, T> {
+    let sign = usize::from(input.starts_with(['-', '+']));
+
+    digits(input, sign)
+}
+
+/// Reads the digits after the `start` bytes of sign at the front of `input`
+fn digits<T: FromStr>(input: &str, start: usize) -> Parsed<'_, T> {
+    let end = input[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(input.len(), |i| start + i);
+
+    // A number running straight into a letter, like `3x00`, is one bad
+    // word rather than a number followed by something else
+    let word_end = input[end..]
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .map_or(input.len(), |i| end + i);
+
+    if end == start || word_end != end {
+        let text = if word_end > end {
+            &input[..word_end]
+        } else {
+            token_at(input)
+        };
+
+        return Err(Unexpected::new(text, "a number"));
+    }
+
+    let (number, rest) = input.split_at(end);
+    let value = number
+        .parse()
+        .map_err(|_| Unexpected::new(number, "a smaller number"))?;
+
+    Ok((value, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_leading_digits() {
+        assert_eq!(unsigned::<u32>("498,4"), Ok((498, ",4")));
+        assert_eq!(signed::<i64>("-2, y=15"), Ok((-2, ", y=15")));
+        assert_eq!(signed::<i64>("+7"), Ok((7, "")));
+    }
+
+    #[test]
+    fn points_at_what_isnt_a_number() {
+        let err = unsigned::<u32>("lots of food").unwrap_err();
+        assert_eq!((err.text, err.expected.as_str()), ("lots", "a number"));
+
+        assert_eq!(unsigned::<u32>("3x00 ").unwrap_err().text, "3x00");
+        assert_eq!(unsigned::<u32>("-3").unwrap_err().text, "-3");
+        assert_eq!(signed::<i32>("-").unwrap_err().text, "-");
+        assert_eq!(unsigned::<u32>("").unwrap_err().text, "");
+    }
+
+    #[test]
+    fn numbers_have_to_fit() {
+        let err = unsigned::<u8>("256").unwrap_err();
+
+        assert_eq!(
+            (err.text, err.expected.as_str()),
+            ("256", "a smaller number")
+        );
+    }
+}
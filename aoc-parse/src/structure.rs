@@ -0,0 +1,223 @@
+use aoc_core::Unexpected;
+use aoc_grid::Grid;
+
+use crate::Parsed;
+
+/// One or more of `item`, with `separator` between each of them
+pub fn separated<'a, T>(
+    item: impl Fn(&'a str) -> Parsed<'a, T>,
+    separator: &'static str,
+) -> impl Fn(&'a str) -> Parsed<'a, Vec<T>> {
+    move |input| {
+        let (first, mut rest) = item(input)?;
+        let mut values = vec![first];
+
+        while let Some(after) = rest.strip_prefix(separator) {
+            let (value, after) = item(after)?;
+            values.push(value);
+            rest = after;
+        }
+
+        Ok((values, rest))
+    }
+}
+
+/// One `item` on each line, which has to use up the whole line. Blank lines
+/// at the very end are left for whoever comes next
+pub fn lines<'a, T>(
+    item: impl Fn(&'a str) -> Parsed<'a, T>,
+) -> impl Fn(&'a str) -> Parsed<'a, Vec<T>> {
+    move |input| {
+        let mut values = vec![];
+        let mut rest = input;
+
+        while !rest.trim_start_matches(['\r', '\n']).is_empty() {
+            let (line, next) = rest.split_once('\n').unwrap_or((rest, ""));
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            let (value, left) = item(line)?;
+            if !left.is_empty() {
+                return Err(Unexpected::new(left, "the end of the line"));
+            }
+
+            values.push(value);
+            rest = next;
+        }
+
+        Ok((values, rest))
+    }
+}
+
+/// One `item` in each block of lines, with blocks split up by a blank line
+/// and each `item` using up its whole block
+pub fn blocks<'a, T>(
+    item: impl Fn(&'a str) -> Parsed<'a, T>,
+) -> impl Fn(&'a str) -> Parsed<'a, Vec<T>> {
+    move |input| {
+        let body = input.trim_end_matches(['\r', '\n']);
+        let mut values = vec![];
+
+        for block in body.split("\n\n") {
+            let (value, left) = item(block)?;
+
+            if let Some(extra) = left.strip_prefix('\n') {
+                return Err(Unexpected::new(extra, "a blank line"));
+            } else if !left.is_empty() {
+                return Err(Unexpected::new(left, "the end of the line"));
+            }
+
+            values.push(value);
+        }
+
+        Ok((values, &input[body.len()..]))
+    }
+}
+
+/// A map drawn with one character per cell, running up to the next blank
+/// line. See [`Grid::parse`]
+pub fn grid<'a, T>(
+    expected: &'static str,
+    cell: impl Fn(char) -> Option<T>,
+) -> impl Fn(&'a str) -> Parsed<'a, Grid<T>> {
+    move |input| {
+        let end = input.find("\n\n").unwrap_or(input.len());
+        let grid = Grid::parse(&input[..end], expected, &cell)?;
+
+        Ok((grid, &input[end..]))
+    }
+}
+
+/// A line that reads exactly like `pattern`, with `hole` parsing each `{}`
+/// in it, like `template("move {} from {} to {}", unsigned)`
+///
+/// The text of each hole runs up to whatever follows it in the pattern, or
+/// to the end of the line for a hole at the end. A line that doesn't match
+/// the pattern is pointed out as a whole, and anything wrong inside a hole
+/// is pointed out on its own.
+pub fn template<'a, T, const N: usize>(
+    pattern: &'static str,
+    hole: impl Fn(&'a str) -> Parsed<'a, T>,
+) -> impl Fn(&'a str) -> Parsed<'a, [T; N]> {
+    assert_eq!(
+        pattern.matches("{}").count(),
+        N,
+        "{pattern:?} should have {N} holes"
+    );
+    assert!(
+        !pattern.contains("{}{}"),
+        "Holes need something between them"
+    );
+
+    move |input| {
+        let (line, rest) = input.split_at(input.find('\n').unwrap_or(input.len()));
+        let mismatch = || Unexpected::new(line, format!("a line like {pattern:?}"));
+
+        let mut literals = pattern.split("{}");
+        let first = literals.next().unwrap_or_default();
+        let mut remaining = line.strip_prefix(first).ok_or_else(mismatch)?;
+        let mut values = Vec::with_capacity(N);
+
+        for literal in literals {
+            let end = if literal.is_empty() {
+                remaining.len()
+            } else {
+                remaining.find(literal).ok_or_else(mismatch)?
+            };
+
+            let (value, left) = hole(&remaining[..end])?;
+            if !left.is_empty() {
+                let expected = if literal.is_empty() {
+                    "the end of the line".to_string()
+                } else {
+                    format!("{literal:?}")
+                };
+                return Err(Unexpected::new(left, expected));
+            }
+
+            values.push(value);
+            remaining = &remaining[end + literal.len()..];
+        }
+
+        if !remaining.is_empty() {
+            return Err(mismatch());
+        }
+
+        match values.try_into() {
+            Ok(values) => Ok((values, rest)),
+            Err(_) => unreachable!("The pattern was checked for {N} holes"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{signed, unsigned};
+
+    #[test]
+    fn separates_items() {
+        let points = separated(unsigned::<u32>, ",");
+
+        assert_eq!(points("1,2,3 -> 4"), Ok((vec![1, 2, 3], " -> 4")));
+        assert_eq!(points("1,,3").unwrap_err().text, ",3");
+    }
+
+    #[test]
+    fn parses_every_line() {
+        let numbers = lines(unsigned::<u32>);
+
+        assert_eq!(numbers("1\n2\r\n3\n\n"), Ok((vec![1, 2, 3], "\n")));
+
+        let err = numbers("1\n2 3\n").unwrap_err();
+        assert_eq!(
+            (err.text, err.expected.as_str()),
+            (" 3", "the end of the line")
+        );
+    }
+
+    #[test]
+    fn parses_every_block() {
+        let elves = blocks(separated(unsigned::<u32>, "\n"));
+        let input = "1\n2\n\n3\n";
+
+        assert_eq!(elves(input), Ok((vec![vec![1, 2], vec![3]], "\n")));
+
+        let pairs = blocks(separated(unsigned::<u32>, ","));
+        let err = pairs("1,2\n3\n\n4").unwrap_err();
+        assert_eq!((err.text, err.expected.as_str()), ("3", "a blank line"));
+    }
+
+    #[test]
+    fn grids_stop_at_a_blank_line() {
+        let (trees, rest) = grid("a digit", |c| c.to_digit(10))("12\n34\n\nmore").unwrap();
+
+        assert_eq!((trees.width(), trees.height()), (2, 2));
+        assert_eq!(rest, "\n\nmore");
+        assert_eq!(
+            grid("a digit", |c| c.to_digit(10))("1x").unwrap_err().text,
+            "x"
+        );
+    }
+
+    #[test]
+    fn fills_in_templates() {
+        let sensor = template("Sensor at x={}, y={}: beacon at x={}, y={}", signed::<i64>);
+        let input = "Sensor at x=2, y=-18: beacon at x=-2, y=15\nnext";
+
+        assert_eq!(sensor(input), Ok(([2, -18, -2, 15], "\nnext")));
+
+        let line = "Sensor at x=2, y=18: beacon is at x=-2, y=15";
+        let err = sensor(line).unwrap_err();
+        assert_eq!(err.text, line);
+
+        let err = sensor("Sensor at x=2, y=1a: beacon at x=-2, y=15").unwrap_err();
+        assert_eq!((err.text, err.expected.as_str()), ("1a", "a number"));
+
+        let moved: Parsed<[u32; 1]> = template("move {}", unsigned)("move 1 more");
+        let err = moved.unwrap_err();
+        assert_eq!(
+            (err.text, err.expected.as_str()),
+            (" more", "the end of the line")
+        );
+    }
+}
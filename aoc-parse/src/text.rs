@@ -0,0 +1,109 @@
+use aoc_core::Unexpected;
+
+use crate::{token_at, Parsed};
+
+/// Exactly `text`
+pub fn tag<'a>(text: &'static str) -> impl Fn(&'a str) -> Parsed<'a, &'a str> {
+    move |input| match input.strip_prefix(text) {
+        Some(rest) => Ok((&input[..text.len()], rest)),
+        None => Err(Unexpected::new(token_at(input), format!("{text:?}"))),
+    }
+}
+
+/// A run of anything but whitespace
+pub fn word(input: &str) -> Parsed<'_, &str> {
+    match token_at(input) {
+        "" => Err(Unexpected::new(&input[..0], "a word")),
+        word if word.trim().is_empty() => Err(Unexpected::new(word, "a word")),
+        word => Ok((word, &input[word.len()..])),
+    }
+}
+
+/// A word made only of characters `allowed` accepts. Anything else before
+/// the next whitespace is pointed out on its own
+pub fn chars<'a>(
+    expected: &'static str,
+    allowed: impl Fn(char) -> bool,
+) -> impl Fn(&'a str) -> Parsed<'a, &'a str> {
+    move |input| {
+        let (word, rest) = word(input).map_err(|e| Unexpected::new(e.text, expected))?;
+
+        match word.char_indices().find(|(_, c)| !allowed(*c)) {
+            Some((i, c)) => Err(Unexpected::new(&word[i..i + c.len_utf8()], expected)),
+            None => Ok((word, rest)),
+        }
+    }
+}
+
+/// Whichever of the `options` the input starts with, checked in order
+pub fn one_of<'a, T: Clone>(
+    options: &'static [(&'static str, T)],
+    expected: &'static str,
+) -> impl Fn(&'a str) -> Parsed<'a, T> {
+    move |input| {
+        options
+            .iter()
+            .find_map(|(text, value)| Some((value.clone(), input.strip_prefix(text)?)))
+            .ok_or_else(|| Unexpected::new(token_at(input), expected))
+    }
+}
+
+/// Everything up to the next `delimiter`, which is skipped over
+pub fn until<'a>(
+    delimiter: &'static str,
+    expected: &'static str,
+) -> impl Fn(&'a str) -> Parsed<'a, &'a str> {
+    move |input| match input.split_once(delimiter) {
+        Some((before, after)) => Ok((before, after)),
+        None => Err(Unexpected::missing_after(input, expected)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_match_exactly() {
+        assert_eq!(tag("$ ")("$ ls"), Ok(("$ ", "ls")));
+
+        let err = tag(" -> ")("->1").unwrap_err();
+        assert_eq!((err.text, err.expected.as_str()), ("->1", "\" -> \""));
+    }
+
+    #[test]
+    fn words_stop_at_whitespace() {
+        assert_eq!(word("b.txt rest"), Ok(("b.txt", " rest")));
+        assert!(word(" b").is_err());
+        assert!(word("").is_err());
+    }
+
+    #[test]
+    fn chars_point_at_the_first_bad_one() {
+        let letters = chars("a letter", |c| c.is_ascii_lowercase());
+
+        assert_eq!(letters("abc\ndef"), Ok(("abc", "\ndef")));
+
+        let err = letters("ab1c d").unwrap_err();
+        assert_eq!((err.text, err.expected.as_str()), ("1", "a letter"));
+    }
+
+    #[test]
+    fn picks_one_of_the_options() {
+        const MOVES: &[(&str, char)] = &[("A", 'r'), ("B", 'p')];
+        let moves = one_of(MOVES, "A or B");
+
+        assert_eq!(moves("B Y"), Ok(('p', " Y")));
+        assert_eq!(moves("X Y").unwrap_err().text, "X");
+    }
+
+    #[test]
+    fn reads_until_a_delimiter() {
+        let stacks = until("\n\n", "a blank line");
+
+        assert_eq!(stacks("a\nb\n\nc"), Ok(("a\nb", "c")));
+
+        let err = stacks("a\nb\n").unwrap_err();
+        assert_eq!((err.text, err.expected.as_str()), ("", "a blank line"));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::Image;
+
+/// The frames of a day's drawing, in order
+///
+/// Long runs can keep just every nth step, so drawing a few thousand steps
+/// doesn't mean holding a few thousand images. The frames aren't even drawn
+/// for the steps that get skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    frames: Vec<Image>,
+    every: usize,
+    steps: usize,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::every(1)
+    }
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An animation that only keeps every `every`th step
+    pub fn every(every: usize) -> Self {
+        assert!(every > 0, "Must keep at least every step");
+
+        Self {
+            frames: vec![],
+            every,
+            steps: 0,
+        }
+    }
+
+    /// Records one step, calling `draw` only if it's a step we keep
+    pub fn step(&mut self, draw: impl FnOnce() -> Image) {
+        if self.steps.is_multiple_of(self.every) {
+            self.push(draw());
+        }
+
+        self.steps += 1;
+    }
+
+    /// Records the final picture, which is always kept
+    pub fn finish(&mut self, image: Image) {
+        self.push(image);
+    }
+
+    fn push(&mut self, image: Image) {
+        if let Some(first) = self.frames.first() {
+            assert_eq!(
+                (image.width(), image.height()),
+                (first.width(), first.height()),
+                "Every frame has to be the same size"
+            );
+        }
+
+        self.frames.push(image);
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// The final picture, for formats that can't animate
+    pub fn last(&self) -> Option<&Image> {
+        self.frames.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_every_nth_step_and_the_finish() {
+        let mut animation = Animation::every(3);
+        let mut drawn = 0;
+
+        for _ in 0..7 {
+            animation.step(|| {
+                drawn += 1;
+                Image::new(1, 1, 0)
+            });
+        }
+        animation.finish(Image::new(1, 1, 0));
+
+        assert_eq!(drawn, 3);
+        assert_eq!(animation.frames().len(), 4);
+    }
+}
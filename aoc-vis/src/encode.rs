@@ -0,0 +1,258 @@
+use std::{
+    borrow::Cow,
+    fmt, fs,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{Animation, Image, Palette};
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownFormat(PathBuf),
+    /// Nothing was drawn, so there's nothing to save
+    Empty,
+    /// GIFs can't be wider or taller than 65535 pixels
+    TooBig {
+        width: usize,
+        height: usize,
+    },
+    Io(io::Error),
+    Encode(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownFormat(path) => write!(
+                f,
+                "Couldn't tell what kind of image {} is, try .ppm, .png or .gif",
+                path.display()
+            ),
+            Error::Empty => write!(f, "Nothing was drawn"),
+            Error::TooBig { width, height } => {
+                write!(f, "A {width}x{height} picture is too big for a GIF")
+            }
+            Error::Io(e) => e.fmt(f),
+            Error::Encode(e) => write!(f, "Couldn't encode the picture: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<png::EncodingError> for Error {
+    fn from(e: png::EncodingError) -> Self {
+        Error::Encode(e.to_string())
+    }
+}
+
+impl From<gif::EncodingError> for Error {
+    fn from(e: gif::EncodingError) -> Self {
+        Error::Encode(e.to_string())
+    }
+}
+
+/// The kinds of file a drawing can be saved as. Only GIFs animate, the others
+/// get the final frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl Format {
+    /// Works the format out from the file's extension
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        match extension.as_str() {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+}
+
+/// Saves a drawing in the format its extension asks for, blowing each pixel
+/// up by `scale` and showing each frame of a GIF for `frame_time`
+pub fn save(
+    animation: &Animation,
+    palette: &Palette,
+    scale: usize,
+    frame_time: Duration,
+    path: &Path,
+) -> Result<(), Error> {
+    let format = Format::from_path(path).ok_or_else(|| Error::UnknownFormat(path.to_owned()))?;
+    let last = animation.last().ok_or(Error::Empty)?;
+
+    let mut out = BufWriter::new(fs::File::create(path)?);
+
+    match format {
+        Format::Ppm => write_ppm(&last.scaled(scale), palette, &mut out)?,
+        Format::Png => write_png(&last.scaled(scale), palette, &mut out)?,
+        Format::Gif => {
+            let frames: Vec<_> = animation.frames().iter().map(|f| f.scaled(scale)).collect();
+            write_gif(&frames, palette, frame_time, &mut out)?
+        }
+    }
+
+    Ok(out.flush()?)
+}
+
+/// Writes a binary PPM, which is simple enough to write by hand
+pub fn write_ppm(image: &Image, palette: &Palette, out: &mut impl Write) -> io::Result<()> {
+    let colours = palette.colours(image.shades());
+
+    write!(out, "P6\n{} {}\n255\n", image.width(), image.height())?;
+
+    let rgb: Vec<u8> = image
+        .indices()
+        .iter()
+        .flat_map(|i| {
+            let colour = colours[*i as usize];
+            [colour.r, colour.g, colour.b]
+        })
+        .collect();
+
+    out.write_all(&rgb)
+}
+
+/// Writes an indexed PNG, with the palette's colours in its colour table
+pub fn write_png(image: &Image, palette: &Palette, out: &mut impl Write) -> Result<(), Error> {
+    let mut encoder = png::Encoder::new(out, image.width() as u32, image.height() as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(colour_table(palette, image.shades()));
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(image.indices())?;
+
+    Ok(())
+}
+
+/// Writes an animated GIF that loops forever
+pub fn write_gif(
+    frames: &[Image],
+    palette: &Palette,
+    frame_time: Duration,
+    out: &mut impl Write,
+) -> Result<(), Error> {
+    let first = frames.first().ok_or(Error::Empty)?;
+    let too_big = || Error::TooBig {
+        width: first.width(),
+        height: first.height(),
+    };
+    let width = u16::try_from(first.width()).map_err(|_| too_big())?;
+    let height = u16::try_from(first.height()).map_err(|_| too_big())?;
+
+    // GIFs count time in hundredths of a second
+    let delay = (frame_time.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+
+    let table = colour_table(palette, first.shades());
+    let mut encoder = gif::Encoder::new(out, width, height, &table)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for image in frames {
+        let frame = gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: Cow::Borrowed(image.indices()),
+            ..gif::Frame::default()
+        };
+
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
+
+fn colour_table(palette: &Palette, shades: u8) -> Vec<u8> {
+    palette
+        .colours(shades)
+        .into_iter()
+        .flat_map(|colour| [colour.r, colour.g, colour.b])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_grid::Coord;
+
+    use super::*;
+    use crate::Pixel;
+
+    fn checkerboard() -> Image {
+        let mut image = Image::new(2, 2, 1);
+        image.set(Coord::new(0, 0), Pixel::Shade(0));
+        image.set(Coord::new(1, 1), Pixel::Highlight);
+
+        image
+    }
+
+    #[test]
+    fn writes_ppm() {
+        let mut out = vec![];
+        write_ppm(&checkerboard(), &Palette::MONO, &mut out).unwrap();
+
+        let header = b"P6\n2 2\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        assert_eq!(&out[header.len()..header.len() + 3], &[0x60, 0x60, 0x60]);
+        assert_eq!(out.len(), header.len() + 2 * 2 * 3);
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let mut out = vec![];
+        write_png(&checkerboard(), &Palette::MONO, &mut out).unwrap();
+
+        let mut reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((info.width, info.height), (2, 2));
+        assert_eq!(&pixels[..4], &[1, 0, 0, 2]);
+    }
+
+    #[test]
+    fn gif_keeps_every_frame() {
+        let frames = vec![Image::new(2, 2, 1), checkerboard()];
+        let mut out = vec![];
+        write_gif(
+            &frames,
+            &Palette::CHRISTMAS,
+            Duration::from_millis(50),
+            &mut out,
+        )
+        .unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 5);
+            count += 1;
+        }
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn formats_come_from_extensions() {
+        assert_eq!(Format::from_path(Path::new("a.GIF")), Some(Format::Gif));
+        assert_eq!(Format::from_path(Path::new("a.png")), Some(Format::Png));
+        assert_eq!(Format::from_path(Path::new("a.jpg")), None);
+        assert_eq!(Format::from_path(Path::new("a")), None);
+    }
+}
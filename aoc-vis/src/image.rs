@@ -0,0 +1,179 @@
+use aoc_grid::{Bounds, Coord, Grid};
+
+/// What to draw in one pixel, which the [`Palette`](crate::Palette) turns into
+/// a colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pixel {
+    Background,
+    /// One of the image's shades, from 0 up to one less than
+    /// [`Image::shades`]
+    Shade(u8),
+    /// Something to pick out from everything else, like a path or a marker
+    Highlight,
+}
+
+/// A picture made of palette indices, with `(0, 0)` in the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    shades: u8,
+    indices: Vec<u8>,
+}
+
+impl Image {
+    /// A blank image that can use `shades` shades between its background and
+    /// highlight
+    pub fn new(width: usize, height: usize, shades: u8) -> Self {
+        assert!(shades <= 254, "Images can't have more than 254 shades");
+
+        Self {
+            width,
+            height,
+            shades,
+            indices: vec![0; width * height],
+        }
+    }
+
+    /// Draws a grid with one pixel per cell
+    pub fn from_grid<T>(grid: &Grid<T>, shades: u8, mut pixel: impl FnMut(&T) -> Pixel) -> Self {
+        let mut image = Self::new(grid.width(), grid.height(), shades);
+
+        for (c, value) in grid.iter() {
+            image.set(c, pixel(value));
+        }
+
+        image
+    }
+
+    /// Draws everything inside `bounds`, with `bounds.min` in the top left
+    pub fn from_bounds(bounds: Bounds, shades: u8, mut pixel: impl FnMut(Coord) -> Pixel) -> Self {
+        let mut image = Self::new(bounds.width(), bounds.height(), shades);
+
+        for c in bounds.coords() {
+            image.set(c - bounds.min, pixel(c));
+        }
+
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn shades(&self) -> u8 {
+        self.shades
+    }
+
+    fn index_of(&self, c: Coord) -> Option<usize> {
+        ((0..self.width as isize).contains(&c.x) && (0..self.height as isize).contains(&c.y))
+            .then(|| c.y as usize * self.width + c.x as usize)
+    }
+
+    /// The pixel at `c`, or `None` when `c` is off the edge
+    pub fn get(&self, c: Coord) -> Option<Pixel> {
+        let index = self.indices[self.index_of(c)?];
+
+        Some(match index {
+            0 => Pixel::Background,
+            i if i > self.shades => Pixel::Highlight,
+            i => Pixel::Shade(i - 1),
+        })
+    }
+
+    /// Draws one pixel. Pixels off the edge are ignored, so things can be
+    /// drawn partly out of view
+    pub fn set(&mut self, c: Coord, pixel: Pixel) {
+        let index = match pixel {
+            Pixel::Background => 0,
+            Pixel::Shade(shade) => {
+                assert!(
+                    shade < self.shades,
+                    "The image only has {} shades",
+                    self.shades
+                );
+                shade + 1
+            }
+            Pixel::Highlight => self.shades + 1,
+        };
+
+        if let Some(i) = self.index_of(c) {
+            self.indices[i] = index;
+        }
+    }
+
+    /// Blows every pixel up into a `factor` by `factor` square, so small
+    /// worlds can be seen
+    pub fn scaled(&self, factor: usize) -> Image {
+        let width = self.width * factor;
+        let indices = (0..self.height * factor)
+            .flat_map(|y| (0..width).map(move |x| (y / factor) * self.width + x / factor))
+            .map(|i| self.indices[i])
+            .collect();
+
+        Image {
+            width,
+            height: self.height * factor,
+            shades: self.shades,
+            indices,
+        }
+    }
+
+    /// The palette index of every pixel, a row at a time
+    pub(crate) fn indices(&self) -> &[u8] {
+        &self.indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_grids_and_bounds() {
+        let grid = Grid::parse("#.\n.#\n", "# or .", |c| Some(c == '#')).unwrap();
+        let image = Image::from_grid(&grid, 1, |wall| {
+            if *wall {
+                Pixel::Shade(0)
+            } else {
+                Pixel::Background
+            }
+        });
+
+        assert_eq!(image.get(Coord::new(0, 0)), Some(Pixel::Shade(0)));
+        assert_eq!(image.get(Coord::new(1, 0)), Some(Pixel::Background));
+        assert_eq!(image.get(Coord::new(2, 0)), None);
+
+        let bounds = Bounds {
+            min: Coord::new(-1, 5),
+            max: Coord::new(1, 6),
+        };
+        let image = Image::from_bounds(bounds, 0, |c| {
+            if c == Coord::new(-1, 5) {
+                Pixel::Highlight
+            } else {
+                Pixel::Background
+            }
+        });
+
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.get(Coord::ORIGIN), Some(Pixel::Highlight));
+    }
+
+    #[test]
+    fn scaling_blows_up_each_pixel() {
+        let mut image = Image::new(2, 1, 0);
+        image.set(Coord::new(1, 0), Pixel::Highlight);
+        image.set(Coord::new(5, 5), Pixel::Highlight);
+
+        let scaled = image.scaled(3);
+
+        assert_eq!((scaled.width(), scaled.height()), (6, 3));
+        assert_eq!(scaled.get(Coord::new(2, 2)), Some(Pixel::Background));
+        assert_eq!(scaled.get(Coord::new(3, 2)), Some(Pixel::Highlight));
+    }
+}
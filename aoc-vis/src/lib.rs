@@ -0,0 +1,32 @@
+//! Pictures of what the solutions get up to
+//!
+//! A day draws its world into an [`Image`] of palette indices rather than
+//! colours, so the same drawing can be shown in any [`Palette`]. Days that
+//! change over time record an [`Animation`], one image per step, which can be
+//! saved as an animated GIF or have its last frame saved as a PPM or PNG.
+
+use aoc_core::{Params, Solution, SolveError};
+
+mod animation;
+mod encode;
+mod image;
+mod palette;
+
+pub use animation::Animation;
+pub use encode::{save, Error, Format};
+pub use image::{Image, Pixel};
+pub use palette::{Palette, Rgb};
+
+/// A day that can draw what it's doing
+pub trait Visualise: Solution {
+    /// The palette that suits the drawing best
+    const PALETTE: Palette = Palette::CHRISTMAS;
+
+    /// Records the steps of solving the puzzle into `animation`, finishing
+    /// with a picture of the answer
+    fn draw(
+        input: &Self::Input,
+        params: &Params,
+        animation: &mut Animation,
+    ) -> Result<(), SolveError>;
+}
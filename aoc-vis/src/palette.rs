@@ -0,0 +1,132 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// A colour written the way CSS would, like `0xd42426`
+    pub const fn hex(rgb: u32) -> Self {
+        Self {
+            r: (rgb >> 16) as u8,
+            g: (rgb >> 8) as u8,
+            b: rgb as u8,
+        }
+    }
+
+    /// The colour `t` of the way from `self` to `other`
+    fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+
+        Rgb {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+        }
+    }
+}
+
+/// The colours to show an [`Image`](crate::Image) in
+///
+/// Shades are spread evenly along the ramp, so a drawing with two shades gets
+/// both ends of it and one with 26 gets a smooth gradient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub name: &'static str,
+    pub background: Rgb,
+    pub ramp: &'static [Rgb],
+    pub highlight: Rgb,
+}
+
+impl Palette {
+    pub const CHRISTMAS: Palette = Palette {
+        name: "christmas",
+        background: Rgb::hex(0x0f0f23),
+        ramp: &[Rgb::hex(0x00cc00), Rgb::hex(0xffffff)],
+        highlight: Rgb::hex(0xd42426),
+    };
+
+    pub const MONO: Palette = Palette {
+        name: "mono",
+        background: Rgb::hex(0x000000),
+        ramp: &[Rgb::hex(0x606060), Rgb::hex(0xc0c0c0)],
+        highlight: Rgb::hex(0xffffff),
+    };
+
+    pub const HEAT: Palette = Palette {
+        name: "heat",
+        background: Rgb::hex(0x000004),
+        ramp: &[Rgb::hex(0x3b0f70), Rgb::hex(0xde4968), Rgb::hex(0xfcfdbf)],
+        highlight: Rgb::hex(0x00ffff),
+    };
+
+    pub const TERRAIN: Palette = Palette {
+        name: "terrain",
+        background: Rgb::hex(0x1a1a40),
+        ramp: &[Rgb::hex(0x2e7d32), Rgb::hex(0xa1887f), Rgb::hex(0xffffff)],
+        highlight: Rgb::hex(0xe53935),
+    };
+
+    pub const ALL: &'static [Palette] = &[
+        Palette::CHRISTMAS,
+        Palette::MONO,
+        Palette::HEAT,
+        Palette::TERRAIN,
+    ];
+
+    pub fn find(name: &str) -> Option<Palette> {
+        Self::ALL.iter().find(|p| p.name == name).copied()
+    }
+
+    /// The colour of every palette index in an image with `shades` shades:
+    /// the background, then the shades, then the highlight
+    pub fn colours(&self, shades: u8) -> Vec<Rgb> {
+        let mut colours = vec![self.background];
+
+        colours.extend((0..shades).map(|shade| self.shade(shade, shades)));
+        colours.push(self.highlight);
+
+        colours
+    }
+
+    fn shade(&self, shade: u8, shades: u8) -> Rgb {
+        let last = self.ramp.len() - 1;
+        if shades <= 1 || last == 0 {
+            return self.ramp[0];
+        }
+
+        let along = shade as f64 / (shades - 1) as f64 * last as f64;
+        let stop = (along.floor() as usize).min(last - 1);
+
+        self.ramp[stop].mix(self.ramp[stop + 1], along - stop as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shades_spread_along_the_ramp() {
+        let colours = Palette::HEAT.colours(5);
+
+        assert_eq!(colours.len(), 7);
+        assert_eq!(colours[0], Palette::HEAT.background);
+        assert_eq!(colours[1], Palette::HEAT.ramp[0]);
+        assert_eq!(colours[3], Palette::HEAT.ramp[1]);
+        assert_eq!(colours[5], Palette::HEAT.ramp[2]);
+        assert_eq!(colours[6], Palette::HEAT.highlight);
+
+        assert_eq!(Palette::MONO.colours(1)[1], Palette::MONO.ramp[0]);
+    }
+
+    #[test]
+    fn finds_palettes_by_name() {
+        for palette in Palette::ALL {
+            assert_eq!(Palette::find(palette.name), Some(*palette));
+        }
+
+        assert_eq!(Palette::find("plaid"), None);
+    }
+}
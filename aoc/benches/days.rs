@@ -0,0 +1,53 @@
+use aoc_core::Params;
+use aoc_days::{Day, DAYS};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Times parsing and each part on their own, for every input built into the
+/// day, so a regression shows up against the step that caused it. `params`
+/// overrides the params used for some of those inputs
+fn bench_day(c: &mut Criterion, day: &Day, params: &[(&str, Params)]) {
+    let mut group = c.benchmark_group(format!("day_{:02}", day.day));
+    // Some of the parts take a good fraction of a second on the real
+    // inputs, so we settle for fewer samples to keep the whole suite quick
+    group.sample_size(10);
+
+    for fixture in day.fixtures {
+        let params = params
+            .iter()
+            .find(|(name, _)| *name == fixture.name)
+            .map(|(_, params)| params.clone())
+            .unwrap_or_default();
+
+        day.steps(fixture.input, &params, &mut |step, run| {
+            group.bench_function(BenchmarkId::new(step.name(), fixture.name), |b| {
+                b.iter(&mut *run)
+            });
+        })
+        .expect("Fixtures should always be solved");
+    }
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    // The example only covers a small corner of the real search area
+    let beacon_exclusion_zone = [
+        ("example", Params::new().with("row", 10).with("max", 20)),
+        (
+            "my",
+            Params::new().with("row", 2_000_000).with("max", 4_000_000),
+        ),
+    ];
+
+    for day in DAYS {
+        let params: &[(&str, Params)] = match day.day {
+            15 => &beacon_exclusion_zone,
+            _ => &[],
+        };
+
+        bench_day(c, day, params);
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+//! Generates a test for every answer in `answers.toml`, so each day and part
+//! shows up (and fails) on its own in `cargo test`
+
+use std::{collections::HashSet, env, fmt::Write, fs, path::Path};
+
+#[path = "src/manifest.rs"]
+mod manifest;
+
+use manifest::Manifest;
+
+fn main() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml");
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let manifest = Manifest::load(&path).unwrap_or_else(|e| panic!("{e}"));
+
+    let mut tests = String::new();
+    let mut names = HashSet::new();
+
+    for answer in &manifest.answers {
+        let input = manifest
+            .input(answer)
+            .canonicalize()
+            .unwrap_or_else(|e| panic!("Couldn't find {}: {e}", answer.input.display()));
+        let stem = input
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .expect("Input files should have UTF-8 names");
+
+        let params = answer
+            .params
+            .iter()
+            .map(|(name, value)| format!(".with({name:?}, {value})"))
+            .collect::<String>();
+
+        for (part, expected) in answer.parts() {
+            let name = format!("day_{:02}_{stem}_part_{part}", answer.day);
+            assert!(
+                names.insert(name.clone()),
+                "{name} is in answers.toml more than once"
+            );
+
+            writeln!(
+                tests,
+                r#"
+#[test]
+fn {name}() {{
+    let input = include_str!({input:?});
+    let params = Params::new(){params};
+
+    assert_eq!(check({day}, {part}, input, &params, {expected:?}), Status::Correct);
+}}"#,
+                day = answer.day,
+            )
+            .unwrap();
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("answers.rs");
+    fs::write(out, tests).unwrap();
+}
@@ -0,0 +1,609 @@
+//! Solves many days and parts at once for `run --all`, checking each answer
+//! against `answers.toml` where we know it
+//!
+//! Parts are solved in parallel on rayon's thread pool, each parsing its
+//! input for itself. With timings everything runs one day at a time instead,
+//! since the allocation counters in [`crate::timings`] are shared by every
+//! thread and would count other parts' allocations too.
+
+use std::{
+    fmt::Write,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicBool, Ordering::Relaxed},
+    time::{Duration, Instant},
+};
+
+use aoc_core::Params;
+use aoc_days::{Day, Error, DAYS};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    manifest::Manifest,
+    timings::{self, nanos, Measurement},
+};
+
+/// What to solve, and how
+#[derive(Debug, Clone)]
+pub struct Options<'a> {
+    /// The built-in input every day is solved against. Days that don't have
+    /// one by that name are skipped
+    pub fixture: &'a str,
+    pub days: RangeInclusive<u8>,
+    pub timings: bool,
+    /// Stop starting new parts once one has gone wrong
+    pub fail_fast: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    /// We don't know the answer for this input, so couldn't check it
+    Unchecked,
+    Failed,
+    /// Not run, because something else already went wrong or the day doesn't
+    /// have the input
+    Skipped,
+}
+
+impl Outcome {
+    pub fn is_failure(self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::Failed)
+    }
+
+    fn of(answer: &Result<String, Error>, expected: Option<&str>) -> Self {
+        match (answer, expected) {
+            (Err(_), _) => Outcome::Failed,
+            (Ok(_), None) => Outcome::Unchecked,
+            (Ok(answer), Some(expected)) if answer == expected => Outcome::Correct,
+            (Ok(_), Some(_)) => Outcome::Wrong,
+        }
+    }
+}
+
+/// How one part of one day went
+#[derive(Debug, Clone, Serialize)]
+pub struct Row {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub status: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Parsing and solving the part, or just solving it with timings, where
+    /// parsing is measured on its own
+    #[serde(rename = "duration_ns", serialize_with = "nanos")]
+    pub duration: Duration,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+}
+
+/// What the day's parse and the part cost, measured one at a time
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Timings {
+    pub parse: Measurement,
+    pub part: Measurement,
+}
+
+impl Row {
+    fn new(day: u8, part: u8, expected: Option<&str>, answer: Result<String, Error>) -> Self {
+        let status = Outcome::of(&answer, expected);
+        let (answer, error) = match answer {
+            Ok(answer) => (Some(answer), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        Self {
+            day,
+            part,
+            answer,
+            expected: expected.map(str::to_string),
+            status,
+            error,
+            duration: Duration::ZERO,
+            timings: None,
+        }
+    }
+
+    /// Not run, with the reason when there's more to it than failing fast
+    fn skipped(job: &Job, why: Option<&Error>) -> Self {
+        Self {
+            day: job.day.day,
+            part: job.part,
+            answer: None,
+            expected: job.expected.map(str::to_string),
+            status: Outcome::Skipped,
+            error: why.map(Error::to_string),
+            duration: Duration::ZERO,
+            timings: None,
+        }
+    }
+
+    fn panicked(job: &Job, what: &str, reason: &str) -> Self {
+        Self {
+            status: Outcome::Failed,
+            error: Some(format!("Day {} {what} panicked: {reason}", job.day.day)),
+            ..Self::skipped(job, None)
+        }
+    }
+}
+
+/// One part of one day to solve, with everything needed to check it
+struct Job<'a> {
+    day: &'a Day,
+    part: u8,
+    input: Result<&'static str, Error>,
+    params: Params,
+    expected: Option<&'a str>,
+}
+
+/// Solves both parts of every day in `options.days`, in order of day and
+/// part whichever order they finish in. It's an error when none of them have
+/// the input, since there'd be nothing to check
+pub fn run(options: &Options, manifest: Option<&Manifest>) -> Result<Vec<Row>, String> {
+    let jobs: Vec<Job> = DAYS
+        .iter()
+        .filter(|day| options.days.contains(&day.day))
+        .flat_map(|day| [1, 2].map(|part| job(day, part, options.fixture, manifest)))
+        .collect();
+
+    if jobs.iter().all(|job| job.input.is_err()) {
+        return Err(format!(
+            "None of days {} to {} have a {:?} input built in",
+            options.days.start(),
+            options.days.end(),
+            options.fixture
+        ));
+    }
+
+    Ok(if options.timings {
+        run_one_at_a_time(&jobs, options)
+    } else {
+        run_in_parallel(&jobs, options)
+    })
+}
+
+/// Some inputs have an entry per part, each with its own params, so we look
+/// for the first entry that knows this part's answer and solve it with that
+/// entry's params. Parts we don't know the answer to use the day's defaults
+fn job<'a>(day: &'a Day, part: u8, fixture: &str, manifest: Option<&'a Manifest>) -> Job<'a> {
+    let known = manifest.and_then(|manifest| {
+        manifest
+            .answers
+            .iter()
+            .filter(|answer| {
+                answer.day == day.day
+                    && answer.input.file_stem().and_then(|stem| stem.to_str()) == Some(fixture)
+            })
+            .find_map(|answer| {
+                answer
+                    .parts()
+                    .find_map(|(known, expected)| (known == part).then_some((answer, expected)))
+            })
+    });
+    let params = known.map_or_else(Params::new, |(answer, _)| {
+        answer
+            .params
+            .iter()
+            .fold(Params::new(), |params, (name, value)| {
+                params.with(name, *value)
+            })
+    });
+
+    Job {
+        day,
+        part,
+        input: day.fixture(fixture),
+        params,
+        expected: known.map(|(_, expected)| expected),
+    }
+}
+
+fn run_in_parallel(jobs: &[Job], options: &Options) -> Vec<Row> {
+    let stop = AtomicBool::new(false);
+
+    jobs.par_iter()
+        .map(|job| {
+            let Job { day, part, .. } = *job;
+
+            let input = match job.input {
+                Ok(input) => input,
+                Err(ref e) => return Row::skipped(job, Some(e)),
+            };
+            if stop.load(Relaxed) {
+                return Row::skipped(job, None);
+            }
+
+            let start = Instant::now();
+            let answer = catch_panic(|| day.solve(part, input, &job.params));
+            let row = match answer {
+                Ok(answer) => Row {
+                    duration: start.elapsed(),
+                    ..Row::new(day.day, part, job.expected, answer)
+                },
+                Err(reason) => Row::panicked(job, &format!("part {part}"), &reason),
+            };
+
+            if options.fail_fast && row.status.is_failure() {
+                stop.store(true, Relaxed);
+            }
+
+            row
+        })
+        .collect()
+}
+
+/// Solves each day with a single parse for both of its parts, measuring
+/// every step
+fn run_one_at_a_time(jobs: &[Job], options: &Options) -> Vec<Row> {
+    let mut rows: Vec<Row> = vec![];
+
+    for pair in jobs.chunks(2) {
+        let input = match pair[0].input {
+            Ok(input) => input,
+            Err(ref e) => {
+                rows.extend(pair.iter().map(|job| Row::skipped(job, Some(e))));
+                continue;
+            }
+        };
+        if options.fail_fast && rows.iter().any(|row| row.status.is_failure()) {
+            rows.extend(pair.iter().map(|job| Row::skipped(job, None)));
+            continue;
+        }
+
+        let day = pair[0].day;
+        let report = |params| catch_panic(|| timings::report(day, input, params, true));
+        // Both parts come out of one report unless they need different
+        // params, when each part gets a report of its own
+        let shared = (pair[0].params == pair[1].params).then(|| report(&pair[0].params));
+
+        for job in pair {
+            let own;
+            let report = match &shared {
+                Some(report) => report,
+                None => {
+                    own = report(&job.params);
+                    &own
+                }
+            };
+            let row = match report {
+                Ok(Ok(report)) => {
+                    let part = if job.part == 1 {
+                        &report.part_1
+                    } else {
+                        &report.part_2
+                    };
+                    let timings = report
+                        .parse
+                        .zip(part.measurement)
+                        .map(|(parse, part)| Timings { parse, part });
+
+                    Row {
+                        duration: timings.map_or(Duration::ZERO, |t| t.part.duration),
+                        timings,
+                        ..Row::new(day.day, job.part, job.expected, Ok(part.answer.clone()))
+                    }
+                }
+                Ok(Err(e)) => Row::new(day.day, job.part, job.expected, Err(e.clone())),
+                // Both parts come from the same parse, so we can't tell which
+                // of them it was
+                Err(reason) => Row::panicked(job, "parsing or solving", reason),
+            };
+
+            rows.push(row);
+        }
+    }
+
+    rows
+}
+
+/// Runs `solve`, giving the reason instead if it panics, so one part that
+/// chokes on its input doesn't take the rest of the batch down with it
+fn catch_panic<T>(solve: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(solve)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("no reason given")
+            .to_string()
+    })
+}
+
+/// Lines the rows up under their headings. Answers over several lines, like
+/// letters drawn on a screen, only show their first line
+pub fn table(rows: &[Row], timings: bool) -> String {
+    let mut headings = vec!["Day", "Part", "Answer", "Expected", "Status", "Duration"];
+    if timings {
+        headings.extend(["Parse", "Allocations", "Peak bytes"]);
+    }
+
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            let answer = match (&row.answer, &row.error) {
+                (Some(answer), _) => first_line(answer),
+                (None, Some(error)) => error.clone(),
+                (None, None) => String::new(),
+            };
+            let status = match row.status {
+                Outcome::Correct => "ok",
+                Outcome::Wrong => "WRONG",
+                Outcome::Unchecked => "unchecked",
+                Outcome::Failed => "FAILED",
+                Outcome::Skipped => "skipped",
+            };
+            let duration = match row.status {
+                Outcome::Skipped => String::new(),
+                _ => format!("{:.2?}", row.duration),
+            };
+
+            let mut cells = vec![
+                row.day.to_string(),
+                row.part.to_string(),
+                answer,
+                row.expected.as_deref().map(first_line).unwrap_or_default(),
+                status.to_string(),
+                duration,
+            ];
+            if let Some(t) = row.timings {
+                cells.extend([
+                    format!("{:.2?}", t.parse.duration),
+                    t.part.allocations.to_string(),
+                    t.part.peak_bytes.to_string(),
+                ]);
+            }
+
+            cells
+        })
+        .collect();
+
+    let widths: Vec<usize> = headings
+        .iter()
+        .enumerate()
+        .map(|(i, heading)| {
+            cells
+                .iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .fold(heading.len(), usize::max)
+        })
+        .collect();
+
+    let mut table = String::new();
+    let headings = headings.iter().map(|heading| heading.to_string());
+    for line in std::iter::once(headings.collect()).chain(cells) {
+        let line: Vec<String> = line;
+        let padded: Vec<String> = line
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+
+        let _ = writeln!(table, "{}", padded.join("  ").trim_end());
+    }
+
+    table
+}
+
+fn first_line(text: &str) -> String {
+    match text.trim_start_matches('\n').split_once('\n') {
+        Some((first, _)) => format!("{first}..."),
+        None => text.to_string(),
+    }
+}
+
+/// Reads a range of days like `3-7`, or a single day like `5`
+pub fn parse_days(s: &str) -> Result<RangeInclusive<u8>, String> {
+    let number = |n: &str| {
+        n.trim()
+            .parse::<u8>()
+            .map_err(|e| format!("{n:?} isn't a day: {e}"))
+    };
+
+    let (first, last) = match s.split_once('-') {
+        Some((first, last)) => (number(first)?, number(last)?),
+        None => (number(s)?, number(s)?),
+    };
+
+    if first > last {
+        return Err(format!("{s:?} ends before it starts"));
+    }
+
+    Ok(first..=last)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn manifest(text: &str) -> Manifest {
+        Manifest {
+            dir: Path::new(env!("CARGO_MANIFEST_DIR")).join(".."),
+            ..toml::from_str(text).unwrap()
+        }
+    }
+
+    #[test]
+    fn checks_every_part_in_range() {
+        let manifest = Manifest::load(&Path::new(crate::BUILT_ROOT).join("answers.toml")).unwrap();
+        let options = Options {
+            fixture: "example",
+            days: 14..=15,
+            timings: false,
+            fail_fast: false,
+        };
+
+        let rows = run(&options, Some(&manifest)).unwrap();
+
+        let checked: Vec<_> = rows.iter().map(|row| (row.day, row.part)).collect();
+        assert_eq!(checked, [(14, 1), (14, 2), (15, 1), (15, 2)]);
+        assert!(
+            rows.iter().all(|row| row.status == Outcome::Correct),
+            "{rows:#?}"
+        );
+    }
+
+    #[test]
+    fn skips_days_without_the_fixture() {
+        for timings in [false, true] {
+            let options = Options {
+                fixture: "larger_example",
+                days: 8..=10,
+                timings,
+                fail_fast: false,
+            };
+
+            let rows = run(&options, None).unwrap();
+            let statuses: Vec<_> = rows
+                .iter()
+                .map(|row| (row.day, row.part, row.status))
+                .collect();
+
+            assert_eq!(
+                statuses,
+                [
+                    (8, 1, Outcome::Skipped),
+                    (8, 2, Outcome::Skipped),
+                    (9, 1, Outcome::Unchecked),
+                    (9, 2, Outcome::Unchecked),
+                    (10, 1, Outcome::Skipped),
+                    (10, 2, Outcome::Skipped),
+                ],
+                "With timings: {timings}"
+            );
+            assert_eq!(
+                rows[0].error.as_deref(),
+                Some("Day 8 doesn't have a \"larger_example\" input built in")
+            );
+        }
+    }
+
+    #[test]
+    fn errors_when_there_is_nothing_to_run() {
+        for (fixture, days) in [("my", 21..=25), ("larger_example", 1..=8)] {
+            let options = Options {
+                fixture,
+                days,
+                timings: false,
+                fail_fast: false,
+            };
+
+            assert!(run(&options, None).is_err(), "{options:?}");
+        }
+    }
+
+    #[test]
+    fn reports_panics_instead_of_unwinding() {
+        assert_eq!(catch_panic(|| 1), Ok(1));
+        assert_eq!(
+            catch_panic(|| -> u8 { panic!("Out of {}", "crates") }),
+            Err("Out of crates".to_string())
+        );
+        assert_eq!(
+            catch_panic(|| -> u8 { panic!("Out of crates") }),
+            Err("Out of crates".to_string())
+        );
+    }
+
+    #[test]
+    fn uses_each_parts_own_params() {
+        let manifest = Manifest::load(&Path::new(crate::BUILT_ROOT).join("answers.toml")).unwrap();
+
+        for timings in [false, true] {
+            let options = Options {
+                fixture: "my",
+                days: 6..=9,
+                timings,
+                fail_fast: false,
+            };
+
+            let rows = run(&options, Some(&manifest)).unwrap();
+            let answers: Vec<_> = rows
+                .iter()
+                .filter(|row| row.day == 6 || row.day == 9)
+                .map(|row| (row.day, row.part, row.answer.as_deref(), row.status))
+                .collect();
+
+            assert_eq!(
+                answers,
+                [
+                    (6, 1, Some("1531"), Outcome::Correct),
+                    (6, 2, Some("2518"), Outcome::Correct),
+                    (9, 1, Some("6498"), Outcome::Correct),
+                    (9, 2, Some("2531"), Outcome::Correct),
+                ],
+                "With timings: {timings}"
+            );
+        }
+    }
+
+    #[test]
+    fn stops_after_a_wrong_answer() {
+        let manifest = manifest(
+            r#"
+            [[answer]]
+            day = 1
+            input = "01-calorie-counting/src/example.input"
+            part_1 = "1"
+            "#,
+        );
+        let options = Options {
+            fixture: "example",
+            days: 1..=2,
+            timings: true,
+            fail_fast: true,
+        };
+
+        let rows = run(&options, Some(&manifest)).unwrap();
+        let statuses: Vec<_> = rows.iter().map(|row| row.status).collect();
+
+        assert_eq!(
+            statuses,
+            [
+                Outcome::Wrong,
+                Outcome::Unchecked,
+                Outcome::Skipped,
+                Outcome::Skipped
+            ]
+        );
+        assert!(rows[0].timings.is_some_and(|t| t.parse.allocations > 0));
+    }
+
+    #[test]
+    fn tables_line_up() {
+        let rows = [
+            Row {
+                duration: Duration::from_millis(3),
+                ..Row::new(10, 2, None, Ok("\n#..#\n.##.\n".to_string()))
+            },
+            Row::new(26, 1, Some("1651"), Err(Error::DayNotSolved(26))),
+        ];
+
+        let table = table(&rows, false);
+        let lines: Vec<_> = table.lines().collect();
+
+        assert_eq!(
+            lines,
+            [
+                "Day  Part  Answer                           Expected  Status     Duration",
+                "10   2     #..#...                                    unchecked  3.00ms",
+                "26   1     There is no solution for day 26  1651      FAILED     0.00ns",
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_day_ranges() {
+        assert_eq!(parse_days("3-7"), Ok(3..=7));
+        assert_eq!(parse_days("5"), Ok(5..=5));
+        assert!(parse_days("7-3").is_err());
+        assert!(parse_days("x").is_err());
+    }
+}
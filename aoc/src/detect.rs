@@ -0,0 +1,135 @@
+//! Works out which day an input belongs to, by trying every day's parser on it
+//!
+//! Some parsers take more than their own day's inputs: a list of numbers is
+//! both a day 1 and a day 8 input, and lines of letters could be rucksacks.
+//! So days whose parser takes the input are ranked by how many of the other
+//! days' built-in inputs they take too, since a picky parser accepting the
+//! input says more than a lenient one. Days whose parser gives up are ranked
+//! after them, by how far into the input they got. A parser that only
+//! complains about something missing from the very end of the input is
+//! counted as not getting anywhere, since that's usually one that splits the
+//! input up before looking at any of it.
+
+use std::{cmp::Reverse, fmt};
+
+use aoc_core::ParseError;
+use aoc_days::{Day, DAYS};
+
+/// How well an input fits one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub day: u8,
+    pub fit: Fit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fit {
+    /// The day's parser took the whole input, along with this many of the
+    /// other days' built-in inputs
+    Parses { lenience: usize },
+    /// The day's parser gave up part way through
+    Fails(ParseError),
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let day = self.day;
+
+        match &self.fit {
+            Fit::Parses { lenience: 0 } => write!(f, "Day {day} parses it"),
+            Fit::Parses { lenience: 1 } => {
+                write!(f, "Day {day} parses it, and 1 input from another day")
+            }
+            Fit::Parses { lenience } => write!(
+                f,
+                "Day {day} parses it, and {lenience} inputs from other days"
+            ),
+            Fit::Fails(e) => e.fmt(f),
+        }
+    }
+}
+
+/// Every day, most likely first
+pub fn rank(input: &str) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = DAYS
+        .iter()
+        .map(|day| Candidate {
+            day: day.day,
+            fit: match day.parse(input) {
+                Ok(()) => Fit::Parses {
+                    lenience: lenience(day),
+                },
+                Err(e) => Fit::Fails(e),
+            },
+        })
+        .collect();
+
+    let end = end(input);
+    candidates.sort_by_key(|candidate| match &candidate.fit {
+        Fit::Parses { lenience } => (0, *lenience, Reverse((0, 0))),
+        Fit::Fails(e) if e.text.is_empty() && (e.line, e.column) == end => (1, 0, Reverse((0, 0))),
+        Fit::Fails(e) => (1, 0, Reverse((e.line, e.column))),
+    });
+
+    candidates
+}
+
+/// The line and column just past the end of the input
+fn end(input: &str) -> (usize, usize) {
+    let last_line = input.rfind('\n').map_or(input, |i| &input[i + 1..]);
+
+    (
+        input.matches('\n').count() + 1,
+        last_line.chars().count() + 1,
+    )
+}
+
+/// How many of the other days' built-in inputs `day` parses
+fn lenience(day: &Day) -> usize {
+    DAYS.iter()
+        .filter(|other| other.day != day.day)
+        .flat_map(|other| other.fixtures)
+        .filter(|fixture| day.parse(fixture.input).is_ok())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The day the input most likely belongs to, if any day's parser takes it
+    fn best(input: &str) -> Option<u8> {
+        rank(input)
+            .into_iter()
+            .find(|candidate| matches!(candidate.fit, Fit::Parses { .. }))
+            .map(|candidate| candidate.day)
+    }
+
+    #[test]
+    fn recognises_every_built_in_input() {
+        for day in DAYS {
+            for fixture in day.fixtures {
+                assert_eq!(
+                    best(fixture.input),
+                    Some(day.day),
+                    "day {} {}",
+                    day.day,
+                    fixture.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ranks_failures_by_how_far_they_got() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9\n";
+        let ranked = rank(input);
+
+        assert_eq!(best(input), None);
+        assert_eq!(ranked[0].day, 15);
+        assert_eq!(
+            ranked[0].fit,
+            Fit::Fails(DAYS[14].parse(input).unwrap_err())
+        );
+    }
+}
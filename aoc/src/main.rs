@@ -0,0 +1,683 @@
+use std::{
+    error::Error,
+    io::{self, IsTerminal},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use aoc_client::Client;
+use aoc_core::{read_input, Params};
+use aoc_vis::{Animation, Palette};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use detect::{Candidate, Fit};
+use manifest::Manifest;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+use verify::Status;
+
+mod batch;
+mod detect;
+mod manifest;
+mod scaffold;
+mod serve;
+mod site;
+mod timings;
+mod verify;
+
+#[global_allocator]
+static ALLOCATOR: timings::Counting = timings::Counting;
+
+/// Where the workspace was when the runner was built, for the tests to find
+/// the answers from
+#[cfg(test)]
+const BUILT_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Run the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    workspace: Workspace,
+
+    #[command(flatten)]
+    logging: Logging,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve one part of a day against an input file, stdin, or one of the
+    /// inputs built into the day's crate
+    Run {
+        /// Which day the input is for. Worked out from the input if it isn't
+        /// given
+        #[arg(long)]
+        day: Option<u8>,
+
+        #[arg(
+            long,
+            required_unless_present = "all",
+            value_parser = clap::value_parser!(u8).range(1..=2),
+        )]
+        part: Option<u8>,
+
+        /// Solve both parts of every day against a built-in input, `my`
+        /// unless `--fixture` says otherwise
+        #[arg(long, conflicts_with_all = ["day", "part", "input", "params"])]
+        all: bool,
+
+        /// Time parsing and each part, and count what they allocate
+        #[arg(long, requires = "all")]
+        timings: bool,
+
+        #[arg(long, value_enum, default_value_t = Format::Text, requires = "all")]
+        format: Format,
+
+        /// Stop starting new parts once one gets a wrong answer or fails
+        #[arg(long, requires = "all")]
+        fail_fast: bool,
+
+        /// Only solve these days, like `3-7` or `5`
+        #[arg(long, value_name = "FIRST-LAST", value_parser = batch::parse_days, requires = "all")]
+        days: Option<RangeInclusive<u8>>,
+
+        /// Where to read the puzzle input from, `-` being stdin. Defaults to
+        /// stdin unless `--fixture` is given
+        #[arg(long, conflicts_with = "fixture")]
+        input: Option<PathBuf>,
+
+        /// A built-in input to run against, like `example` or `my`
+        #[arg(long)]
+        fixture: Option<String>,
+
+        /// Override a param the day reads, like `--param row=10` for day 15
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, i64)>,
+    },
+    /// Work out which day an input is for, listing every day from most to
+    /// least likely
+    Detect {
+        /// Where to read the puzzle input from, `-` being stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Check every solution against the answers we already know
+    Verify {
+        /// Defaults to `answers.toml` at the root of the workspace
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+
+        /// Only check this day
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Start a new day, like `aoc new 21 monkey-math`, with a crate
+    /// to fill in that the runner already knows about
+    New {
+        day: u8,
+
+        /// The puzzle's title in lowercase words joined by hyphens
+        name: String,
+    },
+    /// Print a random input for a day, for stress testing and benchmarks
+    Generate {
+        #[arg(long)]
+        day: u8,
+
+        /// Roughly how many things the input has in it, like elves, moves or
+        /// monkeys, depending on the day
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+
+        /// The same seed always gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Draw what a day does, as a PPM or PNG of how it ends or an animated
+    /// GIF of every step
+    Draw {
+        #[arg(long)]
+        day: u8,
+
+        /// Where to read the puzzle input from, `-` being stdin. Defaults to
+        /// stdin unless `--fixture` is given
+        #[arg(long, conflicts_with = "fixture")]
+        input: Option<PathBuf>,
+
+        /// A built-in input to draw, like `example` or `my`
+        #[arg(long)]
+        fixture: Option<String>,
+
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, i64)>,
+
+        #[command(flatten)]
+        picture: Picture,
+    },
+    /// Serve every solution over HTTP as a JSON API, answering
+    /// `POST /days/{day}/parts/{part}` with the input as the body
+    Serve {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// The address to listen on. Only this machine can connect by default
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+    /// Download a day's input and example from the site
+    Fetch {
+        #[arg(long)]
+        day: u8,
+
+        /// Also save them into the day's crate as `my.input` and
+        /// `example.input`
+        #[arg(long)]
+        save: bool,
+
+        #[command(flatten)]
+        site: Site,
+    },
+    /// Submit an answer to the site
+    Submit {
+        #[arg(long)]
+        day: u8,
+
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// The answer to submit. Defaults to solving the part against the
+        /// day's downloaded input
+        #[arg(long)]
+        answer: Option<String>,
+
+        #[command(flatten)]
+        site: Site,
+    },
+    /// List the answers submitted so far and what the site made of them
+    History {
+        /// Only list this day's answers
+        #[arg(long)]
+        day: Option<u8>,
+
+        #[command(flatten)]
+        site: Site,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Picture {
+    /// The file to save, with an extension of .ppm, .png or .gif
+    #[arg(long)]
+    output: PathBuf,
+
+    /// The colours to draw in, instead of the ones that suit the day best
+    #[arg(long, value_parser = parse_palette)]
+    palette: Option<Palette>,
+
+    /// How many pixels across each cell is drawn
+    #[arg(long, default_value_t = 4)]
+    scale: usize,
+
+    /// Only keep every nth step of an animation, to keep long ones small
+    #[arg(long, default_value_t = 1)]
+    every: usize,
+
+    /// How long each frame of an animation shows for, in milliseconds
+    #[arg(long, default_value_t = 50)]
+    frame_ms: u64,
+}
+
+/// Where the answers, the downloaded inputs and the day crates live
+#[derive(Debug, Args)]
+struct Workspace {
+    /// The root of the workspace. Defaults to the nearest directory with an
+    /// `answers.toml`, starting from the current one and going up
+    #[arg(long, env = "AOC_ROOT", global = true)]
+    root: Option<PathBuf>,
+}
+
+impl Workspace {
+    fn root(&self) -> PathBuf {
+        if let Some(root) = &self.root {
+            return root.clone();
+        }
+
+        let cwd = std::env::current_dir().unwrap_or_default();
+        if let Some(root) = cwd
+            .ancestors()
+            .find(|dir| dir.join("answers.toml").is_file())
+        {
+            return root.to_path_buf();
+        }
+
+        #[cfg(test)]
+        return PathBuf::from(BUILT_ROOT);
+        #[cfg(not(test))]
+        cwd
+    }
+
+    fn answers(&self) -> PathBuf {
+        self.root().join("answers.toml")
+    }
+}
+
+/// What the solutions say about how they're getting on goes to stderr, so it
+/// never gets mixed up with the answers
+#[derive(Debug, Args)]
+struct Logging {
+    /// Say more about what's going on: -v for each day and part as it
+    /// finishes, -vv for progress inside the solutions, -vvv for everything
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    log_format: Format,
+}
+
+impl Logging {
+    fn init(&self) {
+        let level = match self.verbose {
+            0 => LevelFilter::WARN,
+            1 => LevelFilter::INFO,
+            2 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        };
+        let logs = tracing_subscriber::fmt()
+            .with_max_level(level)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_ansi(io::stderr().is_terminal())
+            .with_writer(io::stderr);
+
+        match self.log_format {
+            Format::Text => logs.init(),
+            Format::Json => logs.json().init(),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct Site {
+    /// The `session` cookie of a browser logged in to the site
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+
+    /// Where downloaded inputs and the submission history are kept.
+    /// Defaults to `.aoc-cache` at the root of the workspace
+    #[arg(long)]
+    cache: Option<PathBuf>,
+}
+
+impl Site {
+    fn client(&self, workspace: &Workspace) -> Client {
+        let cache = match &self.cache {
+            Some(cache) => cache.clone(),
+            None => workspace.root().join(".aoc-cache"),
+        };
+
+        Client::new(self.session.clone().unwrap_or_default(), &cache)
+    }
+
+    /// A client for the commands that can't do anything without a session
+    fn logged_in_client(&self, workspace: &Workspace) -> Result<Client, String> {
+        match self.session {
+            Some(_) => Ok(self.client(workspace)),
+            None => Err("Set AOC_SESSION or pass --session to talk to the site".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    cli.logging.init();
+    let workspace = &cli.workspace;
+
+    match cli.command {
+        Command::Run {
+            all: true,
+            timings,
+            format,
+            fail_fast,
+            days,
+            fixture,
+            ..
+        } => run_all(
+            &batch::Options {
+                fixture: fixture.as_deref().unwrap_or("my"),
+                days: days.unwrap_or(1..=25),
+                timings,
+                fail_fast,
+            },
+            format,
+            &workspace.answers(),
+        ),
+        Command::Run {
+            day,
+            part: Some(part),
+            input,
+            fixture,
+            params,
+            ..
+        } => {
+            let params = params
+                .into_iter()
+                .fold(Params::new(), |params, (name, value)| {
+                    params.with(name, value)
+                });
+
+            match run(day, part, input.as_deref(), fixture.as_deref(), &params) {
+                Ok(ans) => {
+                    println!("{ans}");
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("{e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Run { .. } => unreachable!("clap requires a part without --all"),
+        Command::Detect { input } => report(detect(input.as_deref())),
+        Command::Verify { manifest, day } => {
+            verify(&manifest.unwrap_or_else(|| workspace.answers()), day)
+        }
+        Command::New { day, name } => match scaffold::new_day(&workspace.root(), day, &name) {
+            Ok(dir) => {
+                println!("Created {}", dir.display());
+                println!("Paste the puzzle's inputs into src/example.input and src/my.input");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Generate { day, size, seed } => match aoc_gen::generate(day, size, seed) {
+            Some(input) => {
+                print!("{input}");
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("We can't generate inputs for day {day}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Draw {
+            day,
+            input,
+            fixture,
+            params,
+            picture,
+        } => {
+            let params = params
+                .into_iter()
+                .fold(Params::new(), |params, (name, value)| {
+                    params.with(name, value)
+                });
+
+            report(draw(
+                day,
+                input.as_deref(),
+                fixture.as_deref(),
+                &params,
+                &picture,
+            ))
+        }
+        Command::Serve { port, host } => match tiny_http::Server::http((host.as_str(), port)) {
+            Ok(server) => {
+                println!("Listening on http://{}", server.server_addr());
+                serve::serve(&server);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("Couldn't listen on {host}:{port}: {e}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Fetch { day, save, site } => report(
+            site.logged_in_client(workspace)
+                .map_err(Into::into)
+                .and_then(|client| site::fetch(&client, &workspace.root(), day, save)),
+        ),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            site,
+        } => {
+            let verdict = site
+                .logged_in_client(workspace)
+                .map_err(Into::into)
+                .and_then(|client| site::submit(&client, day, part, answer));
+
+            match verdict {
+                Ok(verdict) => {
+                    println!("{verdict}");
+                    if verdict == aoc_client::Verdict::Correct {
+                        ExitCode::SUCCESS
+                    } else {
+                        ExitCode::FAILURE
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::History { day, site } => report(site::history(&site.client(workspace), day)),
+    }
+}
+
+fn report(result: Result<(), Box<dyn Error>>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(
+    day: Option<u8>,
+    part: u8,
+    input: Option<&Path>,
+    fixture: Option<&str>,
+    params: &Params,
+) -> Result<String, Box<dyn Error>> {
+    let (day, input) = match (day, fixture) {
+        (Some(day), Some(name)) => {
+            let day = aoc_days::find(day)?;
+            (day, day.fixture(name)?.to_string())
+        }
+        (None, Some(_)) => return Err("--fixture needs a --day to pick it from".into()),
+        (Some(day), None) => (aoc_days::find(day)?, read(input)?),
+        (None, None) => {
+            let input = read(input)?;
+            (guess_day(&input)?, input)
+        }
+    };
+
+    Ok(day.solve(part, &input, params)?)
+}
+
+fn draw(
+    day: u8,
+    input: Option<&Path>,
+    fixture: Option<&str>,
+    params: &Params,
+    picture: &Picture,
+) -> Result<(), Box<dyn Error>> {
+    let day = aoc_days::find(day)?;
+    let input = match fixture {
+        Some(name) => day.fixture(name)?.to_string(),
+        None => read(input)?,
+    };
+
+    let mut animation = Animation::every(picture.every.max(1));
+    let palette = day.draw(&input, params, &mut animation)?;
+
+    aoc_vis::save(
+        &animation,
+        &picture.palette.unwrap_or(palette),
+        picture.scale.max(1),
+        Duration::from_millis(picture.frame_ms),
+        &picture.output,
+    )
+    .map_err(|e| format!("Couldn't save {}: {e}", picture.output.display()))?;
+
+    println!(
+        "Saved {} ({} frames)",
+        picture.output.display(),
+        animation.frames().len()
+    );
+
+    Ok(())
+}
+
+fn read(input: Option<&Path>) -> Result<String, String> {
+    read_input(input).map_err(|e| match input {
+        Some(path) => format!("Couldn't read {}: {e}", path.display()),
+        None => format!("Couldn't read stdin: {e}"),
+    })
+}
+
+/// Picks the day an input most likely belongs to, explaining the choice on
+/// stderr so it doesn't get mixed up with the answer
+fn guess_day(input: &str) -> Result<&'static aoc_days::Day, Box<dyn Error>> {
+    let ranked = detect::rank(input);
+    let parses = |candidate: &&Candidate| matches!(candidate.fit, Fit::Parses { .. });
+
+    let Some(best) = ranked.iter().find(parses) else {
+        return Err(format!(
+            "No day's parser takes this input, the closest was\n{}",
+            ranked[0]
+        )
+        .into());
+    };
+
+    eprintln!("Solving as day {}: {best}", best.day);
+    for other in ranked.iter().filter(parses).skip(1) {
+        eprintln!("  {other}");
+    }
+
+    Ok(aoc_days::find(best.day)?)
+}
+
+fn detect(input: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    for candidate in detect::rank(&read(input)?) {
+        println!("{candidate}");
+    }
+
+    Ok(())
+}
+
+fn run_all(options: &batch::Options, format: Format, answers: &Path) -> ExitCode {
+    // Without the manifest every answer is still solved, just not checked
+    let manifest = Manifest::load(answers).map_err(|e| eprintln!("{e}")).ok();
+    let rows = match batch::run(options, manifest.as_ref()) {
+        Ok(rows) => rows,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&rows).unwrap()),
+        Format::Text => print!("{}", batch::table(&rows, options.timings)),
+    }
+
+    if rows.iter().any(|row| row.status.is_failure()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn verify(manifest: &Path, only_day: Option<u8>) -> ExitCode {
+    let manifest = match Manifest::load(manifest) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = 0;
+
+    for answer in &manifest.answers {
+        if only_day.is_some_and(|day| day != answer.day) {
+            continue;
+        }
+
+        let path = manifest.input(answer);
+        let input = match std::fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Couldn't read {}: {e}", path.display());
+                failures += 1;
+                continue;
+            }
+        };
+
+        let params = answer
+            .params
+            .iter()
+            .fold(Params::new(), |params, (name, value)| {
+                params.with(name, *value)
+            });
+
+        for (part, expected) in answer.parts() {
+            let name = format!(
+                "Day {} part {part} ({})",
+                answer.day,
+                answer.input.display()
+            );
+
+            match verify::check(answer.day, part, &input, &params, expected) {
+                Status::Correct => println!("{name}: ok"),
+                Status::Wrong(answer) => {
+                    println!("{name}: expected {expected:?} but got {answer:?}");
+                    failures += 1;
+                }
+                Status::Failed(e) => {
+                    println!("{name}: {e}");
+                    failures += 1;
+                }
+            }
+        }
+    }
+
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{failures} answers didn't match");
+        ExitCode::FAILURE
+    }
+}
+
+fn parse_palette(name: &str) -> Result<Palette, String> {
+    Palette::find(name).ok_or_else(|| {
+        let names: Vec<_> = Palette::ALL.iter().map(|p| p.name).collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+fn parse_param(s: &str) -> Result<(String, i64), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE but got {s:?}"))?;
+    let value = value.parse().map_err(|e| format!("{value:?} {e}"))?;
+
+    Ok((name.to_string(), value))
+}
@@ -0,0 +1,76 @@
+//! The answer manifest, `answers.toml` at the root of the repo
+//!
+//! This module is also compiled into `build.rs` to generate a test for every
+//! answer, so it can't lean on anything else in the crate.
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "answer", default)]
+    pub answers: Vec<Answer>,
+    /// Where the manifest was loaded from, which input paths are relative to
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+/// The answers we know for one input file
+#[derive(Debug, Deserialize)]
+pub struct Answer {
+    pub day: u8,
+    /// Relative to the manifest, see [`Manifest::input`]
+    pub input: PathBuf,
+    #[serde(default)]
+    pub params: BTreeMap<String, i64>,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answer {
+    /// Each part we know the answer to, alongside that answer
+    pub fn parts(&self) -> impl Iterator<Item = (u8, &str)> {
+        [(1, &self.part_1), (2, &self.part_2)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some((part, answer.as_deref()?)))
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Read(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Read(path, e) => write!(f, "Couldn't read {}: {e}", path.display()),
+            Error::Parse(path, e) => write!(f, "Couldn't parse {}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = fs::read_to_string(path).map_err(|e| Error::Read(path.to_owned(), e))?;
+        let mut manifest: Manifest =
+            toml::from_str(&text).map_err(|e| Error::Parse(path.to_owned(), e))?;
+
+        manifest.dir = path.parent().unwrap_or(Path::new("")).to_owned();
+
+        Ok(manifest)
+    }
+
+    /// The path to an answer's input file
+    pub fn input(&self, answer: &Answer) -> PathBuf {
+        self.dir.join(&answer.input)
+    }
+}
@@ -0,0 +1,271 @@
+//! Sets up the crate for a new day, and registers it with the workspace and
+//! with `aoc-days`, which the runner, the C library and the benchmarks all
+//! find their days in. The day's example goes into `answers.toml` too, ready
+//! for its answers to be filled in
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/day/Cargo.toml");
+const LIB_TEMPLATE: &str = include_str!("../templates/day/lib.rs");
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidDay(u8),
+    InvalidName(String),
+    AlreadyExists(PathBuf),
+    /// We couldn't find where the other days are listed in a file
+    NoDayList(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidDay(day) => write!(f, "There is no day {day} in Advent of Code"),
+            Error::InvalidName(name) => write!(
+                f,
+                "{name:?} should be the puzzle's title in lowercase words joined by hyphens"
+            ),
+            Error::AlreadyExists(path) => write!(f, "{} already exists", path.display()),
+            Error::NoDayList(path) => {
+                write!(f, "Couldn't find the list of days in {}", path.display())
+            }
+            Error::Io(path, e) => write!(f, "Couldn't update {}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The names a day goes by, all worked out from the slug of its title
+#[derive(Debug, PartialEq, Eq)]
+struct Names {
+    day: u8,
+    /// The directory, like `16-proboscidea-volcanium`
+    dir: String,
+    /// The package, like `proboscidea-volcanium`
+    package: String,
+    /// How other crates refer to it, like `proboscidea_volcanium`
+    krate: String,
+    /// The type implementing `Solution`, like `ProboscideaVolcanium`
+    ty: String,
+    /// The puzzle's title, like `Proboscidea Volcanium`
+    title: String,
+}
+
+impl Names {
+    fn new(day: u8, slug: &str) -> Result<Self, Error> {
+        if !(1..=25).contains(&day) {
+            return Err(Error::InvalidDay(day));
+        }
+
+        let words: Vec<&str> = slug.split('-').collect();
+        let valid_word = |word: &&str| {
+            word.starts_with(|c: char| c.is_ascii_lowercase())
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        };
+        if !words.iter().all(valid_word) {
+            return Err(Error::InvalidName(slug.to_string()));
+        }
+
+        let capitalised: Vec<String> = words
+            .iter()
+            .map(|word| word[..1].to_uppercase() + &word[1..])
+            .collect();
+
+        Ok(Self {
+            day,
+            dir: format!("{day:02}-{slug}"),
+            package: slug.to_string(),
+            krate: slug.replace('-', "_"),
+            ty: capitalised.concat(),
+            title: capitalised.join(" "),
+        })
+    }
+
+    fn fill_in(&self, template: &str) -> String {
+        template
+            .replace("__PACKAGE__", &self.package)
+            .replace("__TYPE__", &self.ty)
+            .replace("__NAME__", &self.title)
+            .replace("__DAY__", &self.day.to_string())
+    }
+}
+
+/// A file that lists every day, and the line to add to it for a new one
+struct DayList {
+    path: PathBuf,
+    is_day: fn(&str) -> bool,
+    line: String,
+}
+
+/// Creates the crate for `day` inside `root`, the root of the workspace, and
+/// registers it. Returns the directory it was created in
+pub fn new_day(root: &Path, day: u8, slug: &str) -> Result<PathBuf, Error> {
+    let names = Names::new(day, slug)?;
+    let dir = root.join(&names.dir);
+
+    if let Some(existing) = existing_day(root, day)? {
+        return Err(Error::AlreadyExists(existing));
+    }
+
+    // Work out every edit before making any of them, so a file we can't
+    // make sense of doesn't leave the workspace half updated
+    let lists = [
+        DayList {
+            path: root.join("Cargo.toml"),
+            is_day: |line: &str| is_day_dir(line.trim().trim_start_matches('"')),
+            line: format!("  \"{}\",", names.dir),
+        },
+        DayList {
+            path: root.join("aoc-days/Cargo.toml"),
+            is_day: |line: &str| {
+                line.contains("path = \"../") && is_day_dir(line.split("../").nth(1).unwrap_or(""))
+            },
+            line: format!("{} = {{ path = \"../{}\" }}", names.package, names.dir),
+        },
+        DayList {
+            path: root.join("aoc-days/src/lib.rs"),
+            is_day: |line: &str| {
+                line.starts_with("    Day::of::<") || line.starts_with("    Day::drawn::<")
+            },
+            line: format!("    Day::of::<{}::{}>(),", names.krate, names.ty),
+        },
+    ];
+
+    let mut updated = vec![];
+    for DayList { path, is_day, line } in lists {
+        let text = fs::read_to_string(&path).map_err(|e| Error::Io(path.clone(), e))?;
+        let text = insert_after_last(&text, is_day, &line).ok_or(Error::NoDayList(path.clone()))?;
+
+        updated.push((path, text));
+    }
+
+    let answers = root.join("answers.toml");
+    let text = fs::read_to_string(&answers).map_err(|e| Error::Io(answers.clone(), e))?;
+    let entry = format!(
+        "[[answer]]\nday = {day}\ninput = \"{}/src/example.input\"\n",
+        names.dir
+    );
+    updated.push((answers, format!("{}\n\n{entry}", text.trim_end())));
+
+    let src = dir.join("src");
+    fs::create_dir_all(&src).map_err(|e| Error::Io(src.clone(), e))?;
+
+    let files = [
+        (dir.join("Cargo.toml"), names.fill_in(CARGO_TEMPLATE)),
+        (src.join("lib.rs"), names.fill_in(LIB_TEMPLATE)),
+        (src.join("example.input"), String::new()),
+        (src.join("my.input"), String::new()),
+    ];
+
+    for (path, contents) in files.into_iter().chain(updated) {
+        fs::write(&path, contents).map_err(|e| Error::Io(path, e))?;
+    }
+
+    Ok(dir)
+}
+
+/// The directory of a day that's already been started, whatever its title
+pub(crate) fn existing_day(root: &Path, day: u8) -> Result<Option<PathBuf>, Error> {
+    let prefix = format!("{day:02}-");
+    let entries = fs::read_dir(root).map_err(|e| Error::Io(root.to_owned(), e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| Error::Io(root.to_owned(), e))?;
+
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            return Ok(Some(entry.path()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Whether `name` starts like the directory of a day, `NN-`
+fn is_day_dir(name: &str) -> bool {
+    let bytes = name.as_bytes();
+
+    bytes.len() > 3 && bytes[..2].iter().all(u8::is_ascii_digit) && bytes[2] == b'-'
+}
+
+/// Adds `line` after the last line that `is_day` picks out, so the new day
+/// goes at the end of the list of days without disturbing anything after it
+fn insert_after_last(text: &str, is_day: impl Fn(&str) -> bool, line: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let last = lines.iter().rposition(|l| is_day(l))?;
+
+    let mut updated: Vec<&str> = lines[..=last].to_vec();
+    updated.push(line);
+    updated.extend(&lines[last + 1..]);
+
+    Some(updated.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works_out_names_from_the_slug() {
+        let names = Names::new(16, "proboscidea-volcanium").unwrap();
+
+        assert_eq!(names.dir, "16-proboscidea-volcanium");
+        assert_eq!(names.krate, "proboscidea_volcanium");
+        assert_eq!(names.ty, "ProboscideaVolcanium");
+        assert_eq!(names.title, "Proboscidea Volcanium");
+
+        assert!(matches!(Names::new(26, "a"), Err(Error::InvalidDay(26))));
+        for slug in ["", "Proboscidea", "two--hyphens", "1st-day", "under_score"] {
+            assert!(
+                matches!(Names::new(16, slug), Err(Error::InvalidName(_))),
+                "{slug:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn creates_and_registers_a_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc-days/src")).unwrap();
+
+        let files = [
+            "Cargo.toml",
+            "answers.toml",
+            "aoc-days/Cargo.toml",
+            "aoc-days/src/lib.rs",
+        ];
+        for file in files {
+            let real = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(file);
+            fs::copy(real, root.join(file)).unwrap();
+        }
+
+        let dir = new_day(&root, 25, "full-of-hot-air").unwrap();
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+
+        assert_eq!(dir, root.join("25-full-of-hot-air"));
+        assert!(read("25-full-of-hot-air/src/lib.rs").contains("const DAY: u8 = 25;"));
+        assert!(read("25-full-of-hot-air/src/example.input").is_empty());
+        assert!(read("Cargo.toml").contains("  \"25-full-of-hot-air\",\n  \"aoc\","));
+        assert!(read("aoc-days/Cargo.toml")
+            .contains("full-of-hot-air = { path = \"../25-full-of-hot-air\" }\n\n"));
+        assert!(read("aoc-days/src/lib.rs")
+            .contains("    Day::of::<full_of_hot_air::FullOfHotAir>(),\n];"));
+        assert!(read("answers.toml").ends_with(
+            "\n\n[[answer]]\nday = 25\ninput = \"25-full-of-hot-air/src/example.input\"\n"
+        ));
+
+        assert!(matches!(
+            new_day(&root, 25, "something-else"),
+            Err(Error::AlreadyExists(_))
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
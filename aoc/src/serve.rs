@@ -0,0 +1,268 @@
+//! A small JSON API over HTTP, so things that aren't written in Rust can run
+//! the solutions too
+//!
+//! - `GET /days` lists every day, with the params and built-in inputs it has
+//! - `POST /days/{day}/parts/{part}` solves a part against the input in the
+//!   request body. Params go in the query string, like `?row=10&max=20`
+//!
+//! Answers come back with how long they took and what they allocated, and
+//! anything that goes wrong comes back as `{"error": ...}`, with where the
+//! input went wrong when it couldn't be parsed. Requests are handled one at a
+//! time, which keeps the allocation counts meaningful.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc_core::{Params, ParseError, SolveError};
+use aoc_days::Error;
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::info;
+
+use crate::timings::{measure, Measurement};
+
+/// Answers requests until the server is unblocked
+pub fn serve(server: &Server) {
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let (status, json) = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => respond(request.method(), request.url(), &body),
+            Err(_) => failure(400, "The body has to be UTF-8 text"),
+        };
+        info!(method = %request.method(), url = request.url(), status, "Answered");
+
+        reply(request, status, json);
+    }
+}
+
+fn reply(request: Request, status: u16, json: serde_json::Value) {
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(json.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+
+    // There's nobody to tell if the client has already gone away
+    let _ = request.respond(response);
+}
+
+#[derive(Debug, Serialize)]
+struct Answer {
+    day: u8,
+    part: u8,
+    answer: String,
+    #[serde(flatten)]
+    measurement: Measurement,
+}
+
+#[derive(Debug, Serialize)]
+struct DayInfo {
+    day: u8,
+    name: &'static str,
+    params: &'static [&'static str],
+    fixtures: Vec<&'static str>,
+}
+
+/// Works out the status and body for a request
+fn respond(method: &Method, url: &str, body: &str) -> (u16, serde_json::Value) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, &segments[..]) {
+        (Method::Get, ["days"]) => {
+            let days: Vec<_> = aoc_days::DAYS
+                .iter()
+                .map(|day| DayInfo {
+                    day: day.day,
+                    name: day.name,
+                    params: day.params,
+                    fixtures: day.fixtures.iter().map(|f| f.name).collect(),
+                })
+                .collect();
+
+            (200, json!(days))
+        }
+        (Method::Post, ["days", day, "parts", part]) => {
+            let (Ok(day), Ok(part)) = (day.parse(), part.parse()) else {
+                return failure(404, "Days and parts are numbers");
+            };
+
+            match parse_query(query) {
+                Ok(params) => solve(day, part, body, &params),
+                Err(e) => failure(400, &e),
+            }
+        }
+        (_, ["days"] | ["days", _, "parts", _]) => failure(405, "That method isn't allowed here"),
+        _ => failure(404, "There's nothing here"),
+    }
+}
+
+fn solve(day: u8, part: u8, input: &str, params: &Params) -> (u16, serde_json::Value) {
+    let day_info = match aoc_days::find(day) {
+        Ok(day) => day,
+        Err(e) => return failure(404, &e.to_string()),
+    };
+
+    // A solution that panics on a strange input shouldn't take the whole
+    // server down with it
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+        measure(|| day_info.solve(part, input, params))
+    }));
+
+    match solved {
+        Ok((Ok(answer), measurement)) => (
+            200,
+            json!(Answer {
+                day,
+                part,
+                answer,
+                measurement,
+            }),
+        ),
+        Ok((Err(Error::Parse(e)), _)) => (
+            422,
+            json!({ "error": e.to_string(), "parse_error": located(&e) }),
+        ),
+        Ok((Err(e), _)) => {
+            let status = match &e {
+                Error::PartNotSolved { .. }
+                | Error::Solve {
+                    error: SolveError::NotSolved,
+                    ..
+                } => 404,
+                // The input parsed fine, but there's nothing in it to find
+                Error::Solve {
+                    error: SolveError::NoAnswer(_),
+                    ..
+                } => 422,
+                _ => 400,
+            };
+
+            failure(status, &e.to_string())
+        }
+        Err(_) => failure(500, &format!("Day {day} part {part} panicked")),
+    }
+}
+
+fn located(e: &ParseError) -> serde_json::Value {
+    json!({
+        "line": e.line,
+        "column": e.column,
+        "text": e.text,
+        "expected": e.expected,
+    })
+}
+
+fn failure(status: u16, message: &str) -> (u16, serde_json::Value) {
+    (status, json!({ "error": message }))
+}
+
+/// Reads params out of a query string like `row=10&max=20`. Names are
+/// lowercased, so `MAX=20` works too
+fn parse_query(query: &str) -> Result<Params, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .try_fold(Params::new(), |params, pair| {
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected NAME=VALUE but got {pair:?}"))?;
+            let value = value
+                .parse()
+                .map_err(|_| format!("The {name} param has to be a whole number"))?;
+
+            Ok(params.with(name.to_lowercase(), value))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use super::*;
+
+    #[test]
+    fn solves_parts() {
+        let input = include_str!("../../15-beacon-exclusion-zone/src/example.input");
+        let (status, json) = respond(&Method::Post, "/days/15/parts/1?row=10&MAX=20", input);
+
+        assert_eq!(status, 200);
+        assert_eq!(json["answer"], "26");
+        assert!(json["duration_ns"].is_u64());
+        assert!(json["allocations"].is_u64());
+    }
+
+    #[test]
+    fn explains_what_went_wrong() {
+        let (status, json) = respond(&Method::Post, "/days/1/parts/1", "1000\nlots\n");
+        assert_eq!(status, 422);
+        assert_eq!(json["parse_error"]["line"], 2);
+        assert_eq!(json["parse_error"]["text"], "lots");
+
+        let (status, json) = respond(&Method::Post, "/days/1/parts/1?row=10", "1000\n");
+        assert_eq!(status, 400);
+        assert_eq!(json["error"], "Day 1 doesn't take a \"row\" param");
+
+        let (status, _) = respond(&Method::Post, "/days/15/parts/1?row=ten", "");
+        assert_eq!(status, 400);
+
+        assert_eq!(respond(&Method::Post, "/days/26/parts/1", "").0, 404);
+        assert_eq!(respond(&Method::Post, "/days/1/parts/3", "").0, 404);
+        assert_eq!(respond(&Method::Get, "/days/1/parts/1", "").0, 405);
+        assert_eq!(respond(&Method::Get, "/nowhere", "").0, 404);
+    }
+
+    #[test]
+    fn explains_inputs_without_an_answer() {
+        // There is no start-of-packet marker to find
+        let (status, json) = respond(&Method::Post, "/days/6/parts/1", "aaaaaaaa\n");
+        assert_eq!(status, 422);
+        assert!(json["error"].as_str().unwrap().starts_with("Day 6: "));
+
+        let (status, json) = respond(&Method::Post, "/days/6/parts/1?window_size=0", "abcd\n");
+        assert_eq!(status, 400);
+        assert_eq!(
+            json["error"],
+            "Day 6: window_size can't be 0, it should be from 1 to 26"
+        );
+    }
+
+    /// Sends a request, and reads its status and JSON body whether it
+    /// worked or not
+    fn send(request: ureq::Request, body: &str) -> (u16, serde_json::Value) {
+        let response = match request.send_string(body) {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => panic!("Couldn't reach the server: {e}"),
+        };
+        let status = response.status();
+
+        (
+            status,
+            serde_json::from_str(&response.into_string().unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn serves_over_http() {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let url = format!("http://{}", server.server_addr());
+        let handle = {
+            let server = Arc::clone(&server);
+            thread::spawn(move || serve(&server))
+        };
+
+        let days = send(ureq::get(&format!("{url}/days")), "");
+        assert_eq!(days.0, 200);
+        assert_eq!(days.1[0]["name"], "Calorie Counting");
+
+        let input = include_str!("../../01-calorie-counting/src/example.input");
+        let answer = send(ureq::post(&format!("{url}/days/1/parts/2")), input);
+        assert_eq!(answer.1["answer"], "45000");
+
+        let failure = send(ureq::post(&format!("{url}/days/1/parts/2")), "x");
+        assert_eq!(failure.0, 422);
+        assert_eq!(failure.1["parse_error"]["column"], 1);
+
+        server.unblock();
+        handle.join().unwrap();
+    }
+}
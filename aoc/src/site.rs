@@ -0,0 +1,75 @@
+//! The commands that talk to the Advent of Code site, through `aoc_client`
+
+use std::{error::Error, fs, path::Path};
+
+use aoc_client::{Client, Verdict};
+use aoc_core::Params;
+
+use crate::scaffold;
+
+/// Downloads the day's input and example into the cache, and copies them into
+/// the day's crate as `my.input` and `example.input` if `save` is set
+pub fn fetch(client: &Client, root: &Path, day: u8, save: bool) -> Result<(), Box<dyn Error>> {
+    let input = client.input(day)?;
+    let example = client.example(day)?;
+
+    if !save {
+        println!(
+            "Downloaded day {day}'s input ({} lines) and example ({} lines)",
+            input.lines().count(),
+            example.lines().count()
+        );
+        return Ok(());
+    }
+
+    let dir = scaffold::existing_day(root, day)?
+        .ok_or_else(|| format!("Day {day} hasn't been started, try `aoc new` first"))?;
+    let src = dir.join("src");
+
+    for (name, text) in [("my.input", &input), ("example.input", &example)] {
+        let path = src.join(name);
+        fs::write(&path, text).map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+        println!("Saved {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Submits an answer, working it out from the day's downloaded input if it
+/// isn't given
+pub fn submit(
+    client: &Client,
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+) -> Result<Verdict, Box<dyn Error>> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let input = client.input(day)?;
+            aoc_days::find(day)?.solve(part, &input, &Params::new())?
+        }
+    };
+
+    println!("Submitting {answer:?} for day {day} part {part}");
+
+    Ok(client.submit(day, part, &answer)?)
+}
+
+/// Lists every answer submitted so far, or just the ones for `day`
+pub fn history(client: &Client, day: Option<u8>) -> Result<(), Box<dyn Error>> {
+    let history = client.history()?;
+
+    for submission in &history.submissions {
+        if day.is_some_and(|day| day != submission.day) {
+            continue;
+        }
+
+        println!(
+            "Day {:>2} part {} {:>16}: {}",
+            submission.day, submission.part, submission.answer, submission.verdict
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+//! Measures how long each step of a solution takes and how much memory it
+//! allocates along the way
+//!
+//! Allocations are counted by [`Counting`], which wraps the system allocator
+//! and is installed as the global allocator in `main.rs`. The counters are
+//! shared by every thread, so measurements only mean something when one
+//! solution is running at a time.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    time::{Duration, Instant},
+};
+
+use aoc_core::Params;
+use aoc_days::{Day, Error, Step};
+use serde::{Serialize, Serializer};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of allocations and of the most memory
+/// in use at once
+pub struct Counting;
+
+impl Counting {
+    fn grew(by: usize) {
+        let in_use = IN_USE.fetch_add(by, Relaxed) + by;
+        PEAK.fetch_max(in_use, Relaxed);
+    }
+
+    fn shrank(by: usize) {
+        IN_USE.fetch_sub(by, Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            Self::grew(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            Self::grew(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrank(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+
+        if !new_ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+
+            if new_size > layout.size() {
+                Self::grew(new_size - layout.size());
+            } else {
+                Self::shrank(layout.size() - new_size);
+            }
+        }
+
+        new_ptr
+    }
+}
+
+/// What one step of a solution cost
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Measurement {
+    #[serde(rename = "duration_ns", serialize_with = "nanos")]
+    pub duration: Duration,
+    /// Allocations, including reallocations, made during the step
+    pub allocations: usize,
+    /// The most memory the step had allocated at once, on top of whatever
+    /// was already allocated when it started
+    pub peak_bytes: usize,
+}
+
+pub(crate) fn nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos() as u64)
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>12} {:>10} allocations {:>12} bytes at peak",
+            format!("{:.2?}", self.duration),
+            self.allocations,
+            self.peak_bytes
+        )
+    }
+}
+
+/// The answers to both parts of a day, and optionally what it took to get
+/// them
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse: Option<Measurement>,
+    pub part_1: Part,
+    pub part_2: Part,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Part {
+    pub answer: String,
+    #[serde(flatten)]
+    pub measurement: Option<Measurement>,
+}
+
+/// Runs `f`, and measures how long it took and what it allocated
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Measurement) {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let in_use = IN_USE.load(Relaxed);
+    PEAK.store(in_use, Relaxed);
+
+    let start = Instant::now();
+    let value = f();
+    let duration = start.elapsed();
+
+    let measurement = Measurement {
+        duration,
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        peak_bytes: PEAK.load(Relaxed).saturating_sub(in_use),
+    };
+
+    (value, measurement)
+}
+
+/// Solves both parts of `day` from one parse of the input, measuring each
+/// step when `timings` is set
+pub fn report(day: &Day, input: &str, params: &Params, timings: bool) -> Result<Report, Error> {
+    let mut measurements = vec![];
+    let [part_1, part_2] = day.steps(input, params, &mut |step, run| {
+        if timings {
+            measurements.push((step, measure(run).1));
+        } else {
+            run();
+        }
+    })?;
+    // Every step was run just above
+    let [part_1, part_2] = [part_1, part_2].map(Option::unwrap_or_default);
+
+    let measured = |step| {
+        measurements
+            .iter()
+            .find(|(measured, _)| *measured == step)
+            .map(|(_, measurement)| *measurement)
+    };
+
+    Ok(Report {
+        day: day.day,
+        parse: measured(Step::Parse),
+        part_1: Part {
+            answer: part_1,
+            measurement: measured(Step::Part1),
+        },
+        part_2: Part {
+            answer: part_2,
+            measurement: measured(Step::Part2),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        let (v, measurement) = measure(|| vec![0u8; 4096]);
+
+        // Other tests allocate at the same time, so we can only check for at
+        // least what we allocated ourselves
+        assert!(measurement.allocations >= 1);
+        assert!(measurement.peak_bytes >= 4096);
+        assert_eq!(v.len(), 4096);
+    }
+
+    #[test]
+    fn reports_both_parts() {
+        let day = aoc_days::find(1).unwrap();
+        let input = day.fixture("example").unwrap();
+
+        let timed = report(day, input, &Params::new(), true).unwrap();
+        assert_eq!(timed.part_1.answer, "24000");
+        assert_eq!(timed.part_2.answer, "45000");
+        assert!(timed.parse.is_some_and(|parse| parse.allocations > 0));
+
+        let untimed = report(day, input, &Params::new(), false).unwrap();
+        assert!(untimed.parse.is_none() && untimed.part_1.measurement.is_none());
+    }
+}
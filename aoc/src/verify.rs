@@ -0,0 +1,49 @@
+use aoc_core::Params;
+use aoc_days::Error;
+
+/// How a solution fared against an answer we already know
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong(String),
+    Failed(Error),
+}
+
+pub fn check(day: u8, part: u8, input: &str, params: &Params, expected: &str) -> Status {
+    match aoc_days::solve(day, part, input, params) {
+        Ok(answer) if answer == expected => Status::Correct,
+        Ok(answer) => Status::Wrong(answer),
+        Err(e) => Status::Failed(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_answers_are_reported() {
+        let input = include_str!("../../01-calorie-counting/src/example.input");
+
+        assert_eq!(
+            check(1, 1, input, &Params::default(), "45000"),
+            Status::Wrong("24000".to_string())
+        );
+    }
+
+    #[test]
+    fn failures_are_reported() {
+        assert_eq!(
+            check(26, 1, "", &Params::default(), "0"),
+            Status::Failed(Error::DayNotSolved(26))
+        );
+    }
+}
+
+/// One test per answer in `answers.toml`, generated by `build.rs`
+#[cfg(test)]
+mod answers {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/answers.rs"));
+}
@@ -0,0 +1,75 @@
+use aoc_core::{Error, Fixture, Params, ParseError, Solution, SolveError};
+use aoc_parse::{lines, parse_all, Parsed};
+
+#[derive(Debug)]
+pub struct PuzzleInput {
+    pub lines: Vec<String>,
+}
+
+impl PuzzleInput {
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        let (lines, rest) = lines(|line: &str| Ok((line.to_string(), &line[line.len()..])))(input)?;
+
+        Ok((Self { lines }, rest))
+    }
+}
+
+pub struct __TYPE__;
+
+impl Solution for __TYPE__ {
+    const DAY: u8 = __DAY__;
+    const NAME: &'static str = "__NAME__";
+    const FIXTURES: &'static [Fixture] = &[
+        Fixture::new("example", include_str!("example.input")),
+        Fixture::new("my", include_str!("my.input")),
+    ];
+
+    type Input = PuzzleInput;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_all(input, PuzzleInput::parse).map_err(|e| e.locate(Self::DAY, input))
+    }
+
+    fn part_1(_input: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        Err(SolveError::NotSolved)
+    }
+
+    fn part_2(_input: &Self::Input, _params: &Params) -> Result<usize, SolveError> {
+        Err(SolveError::NotSolved)
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, Error> {
+    let input = __TYPE__::parse(input)?;
+
+    Ok(__TYPE__::part_1(&input, &Params::default())?)
+}
+
+pub fn part_2(input: &str) -> Result<usize, Error> {
+    let input = __TYPE__::parse(input)?;
+
+    Ok(__TYPE__::part_2(&input, &Params::default())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn parses_the_example() {
+        let input = include_str!("example.input");
+
+        assert!(__TYPE__::parse(input).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "\\PC{0,64}") {
+            let _ = __TYPE__::parse(&input);
+        }
+    }
+}
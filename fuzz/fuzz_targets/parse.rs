@@ -0,0 +1,13 @@
+//! Feeds arbitrary text to every day's parser. Parsers should turn anything
+//! they don't understand into a `ParseError`, never panic
+
+#![no_main]
+
+use aoc_days::DAYS;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: (u8, &str)| {
+    let (day, input) = data;
+
+    let _ = DAYS[day as usize % DAYS.len()].parse(input);
+});